@@ -0,0 +1,29 @@
+//! Compares the generation of many random numbers one at a time with [`ftkit::random_vec`].
+//!
+//! Run with `cargo bench --bench random_vec`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The number of values generated by each benchmark.
+const COUNT: usize = 10_000_000;
+
+/// Runs `f` a few times and returns the fastest duration.
+fn bench(f: impl Fn() -> Vec<i32>) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let one_at_a_time = bench(|| (0..COUNT).map(|_| ftkit::random_number(0..100)).collect());
+    let bulk = bench(|| ftkit::random_vec(0..100, COUNT));
+
+    println!("random_number x {COUNT}: {one_at_a_time:?}");
+    println!("random_vec({COUNT}):     {bulk:?}");
+}
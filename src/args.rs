@@ -1,147 +1,1700 @@
-use std::cell::UnsafeCell;
-use std::mem::MaybeUninit;
-use std::sync::atomic::AtomicU8;
+use std::cell::Cell;
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::*;
+use std::sync::{Mutex, PoisonError};
 use std::{fmt, ops};
 
-// TODO:
-//  All of this `OnceCell<T>` nonsense should be replaced by the standard library's when it is
-//  stabilized. We might even be able to use standard "lazy" type!
+use crate::once_cell::OnceCell;
 
-/// Indicates that a [`OnceCell<T>`] is not yet initialized.
-const UNINIT: u8 = 0;
-/// Indicates that a [`OnceCell<T>`] is currently being initialized.
-const IN_PROGRESS: u8 = 1;
-/// Indicates that a [`OnceCell<T>`] is initialized.
-const INIT: u8 = 2;
+mod spec;
+pub use self::spec::*;
 
-/// A minimal implementation of a "OnceCell".
-struct OnceCell<T> {
-    /// The protected value.
+mod from_args;
+pub use self::from_args::*;
+
+/// The environment variable whose content is inserted before the arguments of the application.
+///
+/// See [`ARGS`] for more information.
+const EXTRA_ARGS_VAR: &str = "FTKIT_ARGS";
+
+/// Replaces the arguments of the form `@path` with the arguments listed in the file at `path`.
+///
+/// See [`Args::enable_response_files`] for more information.
+fn expand_response_files(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut args = args.into_iter();
+    let mut expanded: Vec<String> = args.next().into_iter().collect();
+
+    // Like flags, response files are not recognized after the `--` separator.
+    let mut separated = false;
+    for arg in args {
+        if separated || arg == "--" {
+            separated = true;
+            expanded.push(arg);
+            continue;
+        }
+
+        let contents = arg
+            .strip_prefix('@')
+            .filter(|path| !path.is_empty())
+            .and_then(|path| std::fs::read_to_string(path).ok());
+        match contents {
+            Some(contents) => {
+                let words = split_quoted(&contents);
+                separated = words.iter().any(|word| word == "--");
+                expanded.extend(words);
+            }
+            None => expanded.push(arg),
+        }
+    }
+
+    expanded
+}
+
+/// Splits the contents of a response file, or of the `FTKIT_ARGS` environment variable, into
+/// arguments.
+///
+/// Arguments are separated by whitespace, unless it appears within double or single quotes.
+fn split_quoted(contents: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+
+    for c in contents.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_with(String::new).push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            None if c.is_whitespace() => args.extend(current.take()),
+            None => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    args.extend(current);
+    args
+}
+
+/// Describes the number of arguments accepted by a range, such as `at least 2 arguments`.
+fn describe_count(start: ops::Bound<&usize>, end: ops::Bound<&usize>) -> String {
+    use std::ops::Bound::*;
+
+    let plural = |n: usize| if n == 1 { "argument" } else { "arguments" };
+    let min = match start {
+        Included(&n) => n,
+        Excluded(&n) => n.saturating_add(1),
+        Unbounded => 0,
+    };
+    let max = match end {
+        Included(&n) => Some(n),
+        Excluded(&n) => Some(n.saturating_sub(1)),
+        Unbounded => None,
+    };
+
+    match max {
+        None => format!("at least {min} {}", plural(min)),
+        Some(max) if min == max => format!("{max} {}", plural(max)),
+        Some(max) if min == 0 => format!("at most {max} {}", plural(max)),
+        Some(max) => format!("between {min} and {max} arguments"),
+    }
+}
+
+/// Returns the sorted paths matching `pattern`, which may contain wildcards in its last
+/// component.
+///
+/// See [`Args::expand_globs`] for more information.
+fn expand_glob(pattern: &str) -> Vec<String> {
+    let (dir, name) = match pattern.rfind(['/', '\\']) {
+        Some(i) => pattern.split_at(i + 1),
+        None => ("", pattern),
+    };
+    if !name.contains(['*', '?']) || dir.contains(['*', '?']) {
+        return Vec::new();
+    }
+
+    let Ok(entries) = std::fs::read_dir(if dir.is_empty() { "." } else { dir }) else {
+        return Vec::new();
+    };
+    let mut matches: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|file| glob_match(name, file))
+        .map(|file| format!("{dir}{file}"))
+        .collect();
+    matches.sort();
+    matches
+}
+
+/// Returns whether the file name `name` matches `pattern`, where `*` matches any sequence of
+/// characters and `?` matches a single character.
+///
+/// Names starting with `.` are only matched if `pattern` starts with `.` too.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // When a character does not match, backtrack to the last `*` and let it match one more
+    // character. Earlier `*`s never need to be revisited.
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    star = Some((sp, sn + 1));
+                    p = sp + 1;
+                    n = sn + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Creates the error returned when the path `value`, at position `index`, can't be accessed.
+fn invalid_path(index: usize, value: &str, err: std::io::Error) -> ArgError {
+    let reason = match err.kind() {
+        std::io::ErrorKind::NotFound => "no such file or directory".to_owned(),
+        _ => err.to_string(),
+    };
+    ArgError::Invalid {
+        index,
+        value: value.to_owned(),
+        reason,
+    }
+}
+
+/// Parses `value`, the argument at position `index`, into a value of type `T`.
+fn parse_at<T>(index: usize, value: &str) -> Result<T, ArgError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|err: T::Err| ArgError::Invalid {
+        index,
+        value: value.to_owned(),
+        reason: err.to_string(),
+    })
+}
+
+/// Returns whether `arg` is one of the `|`-separated spellings listed in `name`.
+fn matches_name(name: &str, arg: &str) -> bool {
+    name.split('|').any(|spelling| spelling == arg)
+}
+
+/// Returns the spelling of `name` used to refer to it in error messages.
+///
+/// This is the first long spelling (starting with `--`), or the first spelling if there is none.
+fn preferred_name(name: &str) -> &str {
+    name.split('|')
+        .find(|spelling| spelling.starts_with("--"))
+        .unwrap_or_else(|| name.split('|').next().unwrap_or(name))
+}
+
+/// Returns the number of times the flag `name` appears in `arg`.
+///
+/// Besides exact matches, single-character spellings of `name` (such as `-v`) are counted within
+/// clusters of short flags (such as `-vxv`).
+fn count_flag_in(name: &str, arg: &str) -> usize {
+    if matches_name(name, arg) {
+        return 1;
+    }
+
+    match arg.strip_prefix('-') {
+        Some(cluster) if cluster.len() > 1 && !cluster.starts_with('-') && !is_number(arg) => {
+            cluster
+                .chars()
+                .filter(|&c| {
+                    name.split('|').any(|spelling| {
+                        let mut chars = spelling.chars();
+                        chars.next() == Some('-')
+                            && chars.next() == Some(c)
+                            && chars.next().is_none()
+                    })
+                })
+                .count()
+        }
+        _ => 0,
+    }
+}
+
+/// Returns whether `arg` is a positional argument, as opposed to a flag or an option.
+///
+/// Negative numbers, such as `-5` or `-0.5`, are positional.
+fn is_positional(arg: &str) -> bool {
+    arg == "-" || !arg.starts_with('-') || is_number(arg)
+}
+
+/// Returns whether `arg` is a number written with digits, such as `-5` or `1e3`.
+///
+/// Unlike parsing an `f64`, this rejects `-inf` and `-nan`, which look more like flags.
+fn is_number(arg: &str) -> bool {
+    let digits = arg.trim_start_matches(['-', '+']);
+    digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') && arg.parse::<f64>().is_ok()
+}
+
+/// Strips the directories from `path`.
+///
+/// Both `/` and `\` are treated as separators, regardless of the platform.
+fn basename(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Represents the arguments passed to the application.
+///
+/// See [`ARGS`] more detailed information.
+pub struct Args {
+    /// The cached arguments.
+    ///
+    /// The first time those arguments are accessed, this cell is initialized.
+    cache: OnceCell<Box<[Box<str>]>>,
+    /// The description of the arguments expected by the application, if one was provided.
+    ///
+    /// See [`Args::set_usage`].
+    usage: Mutex<Option<String>>,
+    /// Whether response files should be expanded when the cache is populated.
+    ///
+    /// See [`Args::enable_response_files`].
+    response_files: AtomicBool,
+}
+
+impl Args {
+    /// Creates a new [`Args`] instance.
+    const fn new() -> Self {
+        Self {
+            cache: OnceCell::new(),
+            usage: Mutex::new(None),
+            response_files: AtomicBool::new(false),
+        }
+    }
+
+    /// Forces the cache of this [`Args`] instance to be populated. The content of the now-complete
+    /// cache is returned.
+    fn force(&self) -> &[Box<str>] {
+        if let Some(args) = OVERRIDE.with(Cell::get) {
+            return args;
+        }
+
+        self.cache.get_or_init(|| {
+            // Unlike `std::env::args`, this does not panic when an argument is not valid UTF-8.
+            let mut args: Vec<String> = std::env::args_os()
+                .map(|arg| {
+                    arg.into_string()
+                        .unwrap_or_else(|arg| arg.to_string_lossy().into_owned())
+                })
+                .collect();
+
+            if let Some(extra) = crate::ENV.get(EXTRA_ARGS_VAR) {
+                let at = args.len().min(1);
+                args.splice(at..at, split_quoted(extra));
+            }
+
+            if self.response_files.load(Relaxed) {
+                args = expand_response_files(args);
+            }
+
+            args.into_iter().map(String::into_boxed_str).collect()
+        })
+    }
+
+    /// Enables the expansion of response files.
+    ///
+    /// Once enabled, an argument of the form `@path` is replaced with the arguments listed in the
+    /// file at `path`. This is useful when a program takes too many arguments to comfortably
+    /// type them on the command line. Within the file, arguments are separated by whitespace
+    /// (including newlines), and may be wrapped in double or single quotes to include
+    /// whitespace. Response files are not expanded recursively.
+    ///
+    /// When the file can't be read, the argument is kept as is. The program name and the
+    /// arguments that follow the `--` separator are never expanded.
+    ///
+    /// # Panics
+    ///
+    /// Arguments are read only once, so this function must be called before they are first
+    /// accessed. It panics otherwise.
+    ///
+    /// The check is not synchronized with other threads: when another thread accesses the
+    /// arguments for the first time while this function runs, they may or may not be expanded,
+    /// and no panic is guaranteed. Call this function at the start of `main`, before spawning
+    /// any thread.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./program @params.txt
+    /// ARGS.enable_response_files();
+    /// for arg in ARGS.iter().skip(1) {
+    ///     println!("{arg}");
+    /// }
+    /// ```
+    pub fn enable_response_files(&self) {
+        assert!(
+            !self.cache.is_started(),
+            "response files must be enabled before the arguments are first accessed"
+        );
+        self.response_files.store(true, Relaxed);
+    }
+
+    /// Returns the number of command-line arguments passed to the application.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// println!("count: {}", ARGS.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.force().len()
+    }
+
+    /// Returns whether the process was executed without any arguments.
+    ///
+    /// Note that this is very unlikely. Even when no concrete arguments are passed to the
+    /// process, its name is still used as the first and only argument.
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// assert!(!ARGS.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.force().is_empty()
+    }
+
+    /// Returns the position of the `--` separator, or the number of arguments if there is none.
+    fn separator(&self) -> usize {
+        let args = self.force();
+        args.iter()
+            .skip(1)
+            .position(|arg| &**arg == "--")
+            .map_or(args.len(), |index| index + 1)
+    }
+
+    /// Returns an iterator over the arguments that may be flags or options.
+    ///
+    /// Those are the arguments between the program name and the `--` separator.
+    fn flag_args(&self) -> ArgsIter<'_> {
+        let args = self.force();
+        ArgsIter {
+            inner: args.get(1..self.separator()).unwrap_or_default().iter(),
+        }
+    }
+
+    /// Returns an iterator over the arguments that follow the `--` separator.
+    fn trailing_args(&self) -> ArgsIter<'_> {
+        let args = self.force();
+        ArgsIter {
+            inner: args.get(self.separator() + 1..).unwrap_or_default().iter(),
+        }
+    }
+
+    /// Returns the argument at position `index`, or `None` if there are not enough arguments.
+    ///
+    /// Unlike indexing `ARGS` directly, this function does not panic when the argument is
+    /// missing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// match ARGS.get(1) {
+    ///     Some(name) => println!("Hello, {name}!"),
+    ///     None => println!("usage: {} <name>", &ARGS[0]),
+    /// }
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.force().get(index).map(Box::as_ref)
+    }
+
+    /// Returns the first argument, or `None` if the process was executed without any arguments.
+    ///
+    /// The first argument is usually the path to the program, as it was typed by the user. See
+    /// [`program_name`](Self::program_name) to get the name of the program alone.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// println!("running {}", ARGS.first().unwrap_or("?"));
+    /// ```
+    #[inline]
+    pub fn first(&self) -> Option<&str> {
+        self.force().first().map(Box::as_ref)
+    }
+
+    /// Returns the last argument, or `None` if the process was executed without any arguments.
+    ///
+    /// When no arguments were passed by the user, this is the program path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./copy a.txt b.txt dir/
+    /// if ARGS.len() > 2 {
+    ///     println!("destination: {}", ARGS.last().unwrap());
+    /// }
+    /// ```
+    #[inline]
+    pub fn last(&self) -> Option<&str> {
+        self.force().last().map(Box::as_ref)
+    }
+
+    /// Returns the argument at position `index`, exiting the application if it is missing.
+    ///
+    /// When the argument is missing, `missing <description>` and the usage message of the
+    /// application are printed to the standard error, and the application exits with status `2`
+    /// (see [`exit_usage`](Self::exit_usage)).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// ARGS.set_usage("<file>");
+    /// let file = ARGS.expect_arg(1, "file name");
+    /// println!("{}", std::fs::read_to_string(file).unwrap());
+    /// ```
+    pub fn expect_arg(&self, index: usize, description: &str) -> &str {
+        match self.get(index) {
+            Some(arg) => arg,
+            None => self.exit_usage(&format!("missing {description}")),
+        }
+    }
+
+    /// Returns the argument at position `index`, asking the user for it if it is missing.
+    ///
+    /// When the argument is missing, `prompt` is printed to the standard output and a line is
+    /// read from the standard input (see [`read_line`](crate::read_line)). The line is returned
+    /// without its terminating newline.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if an error occurs whilst reading the standard input of the program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// let name = ARGS.get_or_prompt(1, "What is your name? ");
+    /// println!("Hello, {name}!");
+    /// ```
+    pub fn get_or_prompt(&self, index: usize, prompt: &str) -> String {
+        if let Some(arg) = self.get(index) {
+            return arg.to_owned();
+        }
+
+        print!("{prompt}");
+        // The prompt usually does not end with a newline, and would otherwise stay buffered.
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+
+        let mut line = crate::read_line();
+        let len = line.trim_end_matches(['\n', '\r']).len();
+        line.truncate(len);
+        line
+    }
+
+    /// Parses the argument at position `index` into a value of type `T`.
+    ///
+    /// This works with any type implementing [`FromStr`], such as numbers, `bool` or `char`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::Missing`] if there are not enough arguments, and
+    /// [`ArgError::Invalid`] if the argument can't be parsed into a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// match ARGS.parse::<u32>(1) {
+    ///     Ok(n) => println!("{n} squared is {}", n * n),
+    ///     Err(err) => println!("error: {err}"),
+    /// }
+    /// ```
+    pub fn parse<T>(&self, index: usize) -> Result<T, ArgError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.get(index).ok_or(ArgError::Missing { index })?;
+        parse_at(index, value)
+    }
+
+    /// Returns the argument at position `index` as a path.
+    ///
+    /// The path is not checked in any way. See [`existing_path`](Self::existing_path) and
+    /// [`canonical_path`](Self::canonical_path) for variants that check that it exists.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::Missing`] if there are not enough arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./ext notes.txt
+    /// let path = ARGS.path(1).unwrap();
+    /// println!("{:?}", path.extension());
+    /// ```
+    pub fn path(&self, index: usize) -> Result<PathBuf, ArgError> {
+        self.get(index)
+            .map(PathBuf::from)
+            .ok_or(ArgError::Missing { index })
+    }
+
+    /// Returns the argument at position `index` as a path, checking that it exists.
+    ///
+    /// The path may refer to a file or a directory.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::Missing`] if there are not enough arguments, and
+    /// [`ArgError::Invalid`] if the path does not exist or can't be accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// let path = match ARGS.existing_path(1) {
+    ///     Ok(path) => path,
+    ///     Err(err) => ARGS.exit_usage(&err.to_string()),
+    /// };
+    /// println!("{}", std::fs::read_to_string(path).unwrap());
+    /// ```
+    pub fn existing_path(&self, index: usize) -> Result<PathBuf, ArgError> {
+        let path = self.path(index)?;
+        match std::fs::metadata(&path) {
+            Ok(_) => Ok(path),
+            Err(err) => Err(invalid_path(index, &self[index], err)),
+        }
+    }
+
+    /// Returns the argument at position `index` as an absolute path, checking that it exists.
+    ///
+    /// Relative paths are resolved from the current directory, and symbolic links, `.` and `..`
+    /// are resolved too (see [`std::fs::canonicalize`]).
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::Missing`] if there are not enough arguments, and
+    /// [`ArgError::Invalid`] if the path does not exist or can't be accessed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./where ../notes.txt
+    /// match ARGS.canonical_path(1) {
+    ///     Ok(path) => println!("{}", path.display()),
+    ///     Err(err) => println!("error: {err}"),
+    /// }
+    /// ```
+    pub fn canonical_path(&self, index: usize) -> Result<PathBuf, ArgError> {
+        let path = self.path(index)?;
+        std::fs::canonicalize(path).map_err(|err| invalid_path(index, &self[index], err))
+    }
+
+    /// Returns the name of the program, without the directories leading to it.
+    ///
+    /// This is the first argument with its path components stripped: `./target/debug/hello` and
+    /// `C:\Users\me\hello.exe` become `hello` and `hello.exe`, respectively. An empty
+    /// string is returned if the process was executed without any arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// if ARGS.len() != 2 {
+    ///     println!("usage: {} <file>", ARGS.program_name());
+    /// }
+    /// ```
+    pub fn program_name(&self) -> &str {
+        self.get(0).map_or("", basename)
+    }
+
+    /// Returns the arguments passed to the application as a vector of string slices.
+    ///
+    /// This makes it possible to match the arguments against slice patterns, which is a concise
+    /// way of handling the different ways a program can be called.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// match ARGS.as_strs()[..] {
+    ///     [_, "list"] => println!("listing"),
+    ///     [_, "add", item] => println!("adding {item}"),
+    ///     [_, "remove", ref items @ ..] => println!("removing {items:?}"),
+    ///     _ => ARGS.exit_usage("invalid command"),
+    /// }
+    /// ```
+    pub fn as_strs(&self) -> Vec<&str> {
+        self.iter().collect()
+    }
+
+    /// Returns an owned copy of the arguments passed to the application.
+    ///
+    /// The first argument is usually the name of the program. The returned vector can be
+    /// modified freely, without affecting [`ARGS`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// let mut words = ARGS.to_vec();
+    /// words.remove(0);
+    /// words.sort();
+    /// words.dedup();
+    /// println!("{}", words.join(" "));
+    /// ```
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(str::to_owned).collect()
+    }
+
+    /// Joins the arguments starting at position `from` with spaces.
+    ///
+    /// This is useful when the end of the command line is free text, as with `echo`. An empty
+    /// string is returned if there are no arguments at or after `from`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./say alice hello there
+    /// let name = ARGS.expect_arg(1, "name");
+    /// let message = ARGS.rest(2);
+    /// println!("{name} says: {message}");
+    /// ```
+    pub fn rest(&self, from: usize) -> String {
+        self.force().get(from..).unwrap_or_default().join(" ")
+    }
+
+    /// Describes the arguments expected by the application.
+    ///
+    /// `spec` is the part of the usage message that follows the program name, such as
+    /// `"<file> [--verbose]"`. It is used by [`usage`](Self::usage) and
+    /// [`exit_usage`](Self::exit_usage).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// ARGS.set_usage("<width> <height>");
+    /// if ARGS.len() != 3 {
+    ///     ARGS.exit_usage("expected two arguments");
+    /// }
+    /// ```
+    pub fn set_usage(&self, spec: &str) {
+        *self.usage.lock().unwrap_or_else(PoisonError::into_inner) = Some(spec.to_owned());
+    }
+
+    /// Returns the usage message of the application.
+    ///
+    /// The message has the form `usage: <program> <spec>`, where `<spec>` is the description
+    /// provided to [`set_usage`](Self::set_usage). When no description was provided, only the
+    /// program name is included.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// ARGS.set_usage("<file>");
+    /// println!("{}", ARGS.usage());
+    /// ```
+    pub fn usage(&self) -> String {
+        let spec = self.usage.lock().unwrap_or_else(PoisonError::into_inner);
+        match spec.as_deref() {
+            Some(spec) => format!("usage: {} {spec}", self.program_name()),
+            None => format!("usage: {}", self.program_name()),
+        }
+    }
+
+    /// Prints `msg` and the usage message of the application to the standard error, then exits
+    /// with status `2`.
+    ///
+    /// The exit status `2` is conventionally used by command-line tools to report invalid
+    /// arguments. When `msg` is empty, only the usage message is printed. See
+    /// [`usage`](Self::usage) for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// ARGS.set_usage("<number>");
+    /// let n: i32 = match ARGS.parse(1) {
+    ///     Ok(n) => n,
+    ///     Err(err) => ARGS.exit_usage(&err.to_string()),
+    /// };
+    /// ```
+    pub fn exit_usage(&self, msg: &str) -> ! {
+        if !msg.is_empty() {
+            eprintln!("error: {msg}");
+        }
+        eprintln!("{}", self.usage());
+        std::process::exit(2)
+    }
+
+    /// Checks that the number of arguments passed by the user is within `range`, exiting the
+    /// application otherwise.
+    ///
+    /// The program name is not counted. When the count is out of range, an error message and
+    /// the usage message of the application are printed to the standard error, and the
+    /// application exits with status `2` (see [`exit_usage`](Self::exit_usage)).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// ARGS.set_usage("<file> [files...]");
+    /// ARGS.require_count(1..);
+    /// for file in ARGS.iter().skip(1) {
+    ///     println!("{file}");
+    /// }
+    /// ```
+    pub fn require_count(&self, range: impl ops::RangeBounds<usize>) {
+        let count = self.len().saturating_sub(1);
+        if !range.contains(&count) {
+            self.exit_usage(&format!(
+                "expected {}, got {count}",
+                describe_count(range.start_bound(), range.end_bound())
+            ));
+        }
+    }
+
+    /// Returns whether the flag `name` was passed to the application.
+    ///
+    /// `name` may list several spellings of the same flag, separated by `|`. For example,
+    /// `"-v|--verbose"` matches both `-v` and `--verbose`. The flag may appear anywhere after the
+    /// program name, but not after the `--` separator: arguments that follow it are always
+    /// positional.
+    ///
+    /// Single-character flags are also recognized within clusters of short flags, as with most
+    /// Unix tools: `-la` is the same as `-l -a`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// if ARGS.flag("-v|--verbose") {
+    ///     println!("Verbose mode enabled.");
+    /// }
+    ///
+    /// // ./ls -la
+    /// let long = ARGS.flag("-l");
+    /// let all = ARGS.flag("-a");
+    /// ```
+    pub fn flag(&self, name: &str) -> bool {
+        self.flag_args().any(|arg| count_flag_in(name, arg) != 0)
+    }
+
+    /// Returns the number of times the flag `name` was passed to the application.
+    ///
+    /// Like with [`flag`](Self::flag), `name` may list several spellings of the same flag,
+    /// separated by `|`. Single-character flags are also counted within clusters of short flags:
+    /// `-vvv` counts as three occurrences of `-v`, and `-xv` as one. This is commonly used to
+    /// choose a level of verbosity.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./program -vv
+    /// match ARGS.count_flag("-v|--verbose") {
+    ///     0 => (),
+    ///     1 => println!("Verbose mode enabled."),
+    ///     _ => println!("Very verbose mode enabled."),
+    /// }
+    /// ```
+    pub fn count_flag(&self, name: &str) -> usize {
+        self.flag_args().map(|arg| count_flag_in(name, arg)).sum()
+    }
+
+    /// Finds the occurrences of the option `name`.
+    ///
+    /// For each occurrence, the spelling of the option that was used is returned along with its
+    /// value, if any. The argument that follows the option is only used as its value if it is
+    /// positional, so that a flag is never swallowed.
+    fn option_occurrences(&self, name: &str) -> Vec<(&str, Option<&str>)> {
+        let mut occurrences = Vec::new();
+        let mut args = self.flag_args().peekable();
+        while let Some(arg) = args.next() {
+            if matches_name(name, arg) {
+                occurrences.push((arg, args.next_if(|next| is_positional(next))));
+            } else if let Some((key, value)) = arg.split_once('=') {
+                if matches_name(name, key) {
+                    occurrences.push((key, Some(value)));
+                }
+            }
+        }
+        occurrences
+    }
+
+    /// Finds the first occurrence of the option `name`.
+    ///
+    /// The spelling of the option that was used is returned along with its value, if any.
+    fn find_option(&self, name: &str) -> Option<(&str, Option<&str>)> {
+        self.option_occurrences(name).into_iter().next()
+    }
+
+    /// Returns whether `arg` was passed to the application.
+    ///
+    /// Only exact matches are considered, and the program name is ignored. Unlike
+    /// [`flag`](Self::flag), `arg` is a single argument: `|` has no special meaning.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// if ARGS.contains("--help") {
+    ///     println!("usage: {} [--help]", ARGS.program_name());
+    ///     return;
+    /// }
+    /// ```
+    pub fn contains(&self, arg: &str) -> bool {
+        self.iter().skip(1).any(|a| a == arg)
+    }
+
+    /// Returns the value of the option `name`, if it was passed to the application.
+    ///
+    /// The value may be provided either in the following argument (`--name value`), or in the
+    /// same argument, after an equal sign (`--name=value`). Like with [`flag`](Self::flag),
+    /// `name` may list several spellings of the same option, separated by `|`. When the option
+    /// appears several times, the first occurrence is used.
+    ///
+    /// `None` is returned if the option is missing, or if it has no value. In the `--name value`
+    /// form, the following argument is only used as the value if it is positional (see
+    /// [`positional`](Self::positional)): in `--name --verbose`, `--name` has no value and
+    /// `--verbose` remains a flag. Options that follow the `--` separator are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./greet --name Alice
+    /// // ./greet -n=Alice
+    /// let name = ARGS.option("-n|--name").unwrap_or("stranger");
+    /// println!("Hello, {name}!");
+    /// ```
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.find_option(name)?.1
+    }
+
+    /// Returns the value of the option `name`, or `default` if it was not passed to the
+    /// application.
+    ///
+    /// See [`option`](Self::option) for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// let color = ARGS.option_or("--color", "auto");
+    /// println!("color mode: {color}");
+    /// ```
+    pub fn option_or<'a>(&'a self, name: &str, default: &'a str) -> &'a str {
+        self.option(name).unwrap_or(default)
+    }
+
+    /// Parses the value of the option `name` into a value of type `T`, or returns `default` if
+    /// it was not passed to the application.
+    ///
+    /// See [`option_parse`](Self::option_parse) for more information.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::MissingValue`] if the option has no value, and
+    /// [`ArgError::InvalidOption`] if its value can't be parsed into a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// let width: usize = ARGS.option_or_parse("--width", 80).unwrap();
+    /// println!("{}", "-".repeat(width));
+    /// ```
+    pub fn option_or_parse<T>(&self, name: &str, default: T) -> Result<T, ArgError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        Ok(self.option_parse(name)?.unwrap_or(default))
+    }
+
+    /// Returns the values of every occurrence of the option `name`, in order.
+    ///
+    /// This is useful for options that can be repeated, such as `--include a --include b`. See
+    /// [`option`](Self::option) for the syntax of options. Occurrences without a value are
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./search --include src --include=tests pattern
+    /// for dir in ARGS.options("-I|--include") {
+    ///     println!("searching in {dir}");
+    /// }
+    /// ```
+    pub fn options(&self, name: &str) -> Vec<&str> {
+        self.option_occurrences(name)
+            .into_iter()
+            .filter_map(|(_, value)| value)
+            .collect()
+    }
+
+    /// Parses the value of the option `name` into a value of type `T`.
+    ///
+    /// See [`option`](Self::option) for the syntax of options. `Ok(None)` is returned if the
+    /// option was not passed to the application.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::MissingValue`] if the option has no value, and
+    /// [`ArgError::InvalidOption`] if its value can't be parsed into a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./countdown --from 10
+    /// let from = match ARGS.option_parse::<u32>("--from") {
+    ///     Ok(from) => from.unwrap_or(3),
+    ///     Err(err) => {
+    ///         println!("error: {err}");
+    ///         return;
+    ///     }
+    /// };
+    ///
+    /// for i in (1..=from).rev() {
+    ///     println!("{i}...");
+    /// }
+    /// ```
+    pub fn option_parse<T>(&self, name: &str) -> Result<Option<T>, ArgError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let Some((option, value)) = self.find_option(name) else {
+            return Ok(None);
+        };
+
+        let value = value.ok_or_else(|| ArgError::MissingValue {
+            option: option.to_owned(),
+        })?;
+
+        match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(err) => Err(ArgError::InvalidOption {
+                option: option.to_owned(),
+                value: value.to_owned(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    /// Returns an iterator over the positional arguments passed to the application.
+    ///
+    /// Positional arguments are all the arguments except the program name and flags, which
+    /// start with `-`. A lone `-` is kept, as it is commonly used to designate the standard
+    /// input, and so are negative numbers such as `-5`.
+    ///
+    /// Every argument that follows the `--` separator is positional, even if it starts with `-`.
+    /// This makes it possible to pass file names such as `-file.txt`, as in
+    /// `./program -- -file.txt`. The separator itself is skipped.
+    ///
+    /// Note that the value of an option written as `--name value` is considered positional, as
+    /// there is no way to know whether `--name` expects a value. Prefer the `--name=value` syntax
+    /// when mixing options and positional arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./cat -n file1.txt file2.txt
+    /// for file in ARGS.positional() {
+    ///     println!("{}", std::fs::read_to_string(file).unwrap());
+    /// }
+    /// ```
+    pub fn positional(&self) -> impl Iterator<Item = &str> {
+        self.flag_args()
+            .filter(|arg| is_positional(arg))
+            .chain(self.trailing_args())
+    }
+
+    /// Returns the positional arguments passed to the application, with wildcards expanded.
+    ///
+    /// Unix shells replace patterns such as `*.txt` with the matching file names before running
+    /// the application, but the Windows command prompt passes them as is. On Windows, this
+    /// function expands them itself, so that programs behave the same on every platform. On
+    /// other platforms, this is the same as collecting [`positional`](Self::positional).
+    ///
+    /// `*` matches any sequence of characters and `?` matches a single character. Only the last
+    /// component of a path may contain wildcards. Names starting with `.` are only matched by
+    /// patterns that start with `.` too. Matches are sorted, and a pattern that matches nothing
+    /// is kept as is, as Unix shells do.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./wc *.txt
+    /// for file in ARGS.expand_globs() {
+    ///     let lines = std::fs::read_to_string(&file).unwrap().lines().count();
+    ///     println!("{lines} {file}");
+    /// }
+    /// ```
+    pub fn expand_globs(&self) -> Vec<String> {
+        let mut expanded = Vec::new();
+        for arg in self.positional() {
+            let matches = if cfg!(windows) {
+                expand_glob(arg)
+            } else {
+                Vec::new()
+            };
+            if matches.is_empty() {
+                expanded.push(arg.to_owned());
+            } else {
+                expanded.extend(matches);
+            }
+        }
+        expanded
+    }
+
+    /// Returns the position of the subcommand, if any.
+    fn subcommand_index(&self) -> Option<usize> {
+        let args = self.force();
+        let separator = self.separator();
+        (1..separator)
+            .find(|&index| is_positional(&args[index]))
+            .or_else(|| Some(separator + 1).filter(|&index| index < args.len()))
+    }
+
+    /// Returns the subcommand passed to the application, if any.
+    ///
+    /// The subcommand is the first positional argument (see [`positional`](Self::positional)).
+    /// Programs such as `git` or `cargo` use subcommands to select what they should do, as in
+    /// `git add` or `cargo build`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// match ARGS.subcommand() {
+    ///     Some("add") => println!("adding {:?}", ARGS.subcommand_args().collect::<Vec<_>>()),
+    ///     Some("list") => println!("listing"),
+    ///     Some(other) => println!("unknown command: {other}"),
+    ///     None => println!("usage: {} <add|list>", ARGS.program_name()),
+    /// }
+    /// ```
+    pub fn subcommand(&self) -> Option<&str> {
+        self.subcommand_index().map(|index| &self[index])
+    }
+
+    /// Returns an iterator over the arguments that follow the subcommand.
+    ///
+    /// The iterator is empty if there is no subcommand. See [`subcommand`](Self::subcommand) for
+    /// more information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
     ///
-    /// # Safety
+    /// // ./todo add "buy milk" "walk the dog"
+    /// if ARGS.subcommand() == Some("add") {
+    ///     for task in ARGS.subcommand_args() {
+    ///         println!("new task: {task}");
+    ///     }
+    /// }
+    /// ```
+    pub fn subcommand_args(&self) -> ArgsIter<'_> {
+        let args = self.force();
+        let start = self
+            .subcommand_index()
+            .map_or(args.len(), |index| index + 1);
+        ArgsIter {
+            inner: args[start..].iter(),
+        }
+    }
+
+    /// Returns an iterator over the arguments passed to the application, classified as flags,
+    /// options and positional arguments.
+    ///
+    /// The program name is skipped. Arguments are classified without knowing which flags and
+    /// options the application expects:
+    ///
+    /// - `--name=value` and `-n=value` are [`ParsedArg::Option`]s.
+    /// - Other arguments starting with `-` are [`ParsedArg::Flag`]s. Clusters of short flags
+    ///   such as `-la` are not split.
+    /// - Everything else is a [`ParsedArg::Positional`], including `-`, negative numbers, and
+    ///   every argument that follows the `--` separator. The separator itself is skipped.
+    ///
+    /// Like with [`positional`](Self::positional), the value of an option written as
+    /// `--name value` is a positional argument that follows the flag `--name`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::{ParsedArg, ARGS};
+    ///
+    /// // ./draw --color=red -v circle
+    /// for arg in ARGS.parsed() {
+    ///     match arg {
+    ///         ParsedArg::Flag("-v" | "--verbose") => println!("verbose"),
+    ///         ParsedArg::Option("--color", color) => println!("color: {color}"),
+    ///         ParsedArg::Positional(shape) => println!("shape: {shape}"),
+    ///         _ => ARGS.exit_usage(&format!("unexpected argument `{arg}`")),
+    ///     }
+    /// }
+    /// ```
+    pub fn parsed(&self) -> impl Iterator<Item = ParsedArg<'_>> {
+        self.flag_args()
+            .map(|arg| {
+                if is_positional(arg) {
+                    ParsedArg::Positional(arg)
+                } else if let Some((name, value)) = arg.split_once('=') {
+                    ParsedArg::Option(name, value)
+                } else {
+                    ParsedArg::Flag(arg)
+                }
+            })
+            .chain(self.trailing_args().map(ParsedArg::Positional))
+    }
+
+    /// Returns an iterator over the arguments passed to the application.
+    ///
+    /// The first argument is usually the name of the program.
     ///
-    /// * If `state` is `UNINIT`, the value is not initialized, but not borrowed in any way.
+    /// # Examples
     ///
-    /// * If `state` is `IN_PROGRESS`, the value is not initialized yet, but is currently borrowed
-    /// exclusively.
+    /// ```no_run
+    /// use ftkit::ARGS;
     ///
-    ///  * If `state` is `INIT`, the value is initialized, but potentially borrowed.
-    value: MaybeUninit<UnsafeCell<T>>,
-    /// The internal state of the once cell.
-    state: AtomicU8,
+    /// let numbers: Vec<i32> = ARGS.iter().skip(1).filter_map(|arg| arg.parse().ok()).collect();
+    /// println!("sum: {}", numbers.iter().sum::<i32>());
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> ArgsIter<'_> {
+        ArgsIter {
+            inner: self.force().iter(),
+        }
+    }
+}
+
+impl fmt::Debug for Args {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.force(), f)
+    }
+}
+
+impl ops::Index<usize> for Args {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.force()[index]
+    }
+}
+
+/// Implements [`ops::Index`] for [`Args`] with the provided range types.
+macro_rules! impl_index_range {
+    ($($range:ty),*) => {
+        $(
+            impl ops::Index<$range> for Args {
+                type Output = [Box<str>];
+
+                #[inline]
+                fn index(&self, index: $range) -> &Self::Output {
+                    &self.force()[index]
+                }
+            }
+        )*
+    };
+}
+
+impl_index_range!(
+    ops::Range<usize>,
+    ops::RangeFrom<usize>,
+    ops::RangeTo<usize>,
+    ops::RangeFull,
+    ops::RangeInclusive<usize>,
+    ops::RangeToInclusive<usize>
+);
+
+/// An argument passed to the application, classified by [`Args::parsed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParsedArg<'a> {
+    /// A flag, such as `-v` or `--verbose`.
+    Flag(&'a str),
+    /// An option with its value, such as `--width=80`.
+    Option(&'a str, &'a str),
+    /// A positional argument.
+    Positional(&'a str),
+}
+
+impl fmt::Display for ParsedArg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Flag(name) => f.write_str(name),
+            Self::Option(name, value) => write!(f, "{name}={value}"),
+            Self::Positional(arg) => f.write_str(arg),
+        }
+    }
+}
+
+/// An error that occurs when an argument can't be retrieved.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ftkit::{ArgError, ARGS};
+///
+/// match ARGS.parse::<i32>(1) {
+///     Ok(n) => println!("{n}"),
+///     Err(ArgError::Missing { .. }) => println!("please provide a number"),
+///     Err(err) => println!("{err}"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArgError {
+    /// The argument at position `index` was not provided.
+    Missing {
+        /// The position of the missing argument.
+        index: usize,
+    },
+    /// The argument at position `index` does not have a valid value.
+    Invalid {
+        /// The position of the invalid argument.
+        index: usize,
+        /// The value of the argument.
+        value: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+    /// The option `option` was provided without a value.
+    MissingValue {
+        /// The option, as it was spelled on the command line.
+        option: String,
+    },
+    /// The option `option` does not have a valid value.
+    InvalidOption {
+        /// The option, as it was spelled on the command line.
+        option: String,
+        /// The value of the option.
+        value: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+    /// The argument named `name` was not provided.
+    MissingNamed {
+        /// The name of the missing argument.
+        name: String,
+    },
+    /// The argument named `name` does not have a valid value.
+    InvalidNamed {
+        /// The name of the invalid argument.
+        name: String,
+        /// The value of the argument.
+        value: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+    /// The argument `arg` was not expected by the application.
+    Unexpected {
+        /// The unexpected argument.
+        arg: String,
+    },
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing { index } => write!(f, "missing argument #{index}"),
+            Self::Invalid {
+                index,
+                value,
+                reason,
+            } => write!(f, "invalid value `{value}` for argument #{index}: {reason}"),
+            Self::MissingValue { option } => write!(f, "missing value for option `{option}`"),
+            Self::InvalidOption {
+                option,
+                value,
+                reason,
+            } => write!(f, "invalid value `{value}` for option `{option}`: {reason}"),
+            Self::MissingNamed { name } => write!(f, "missing argument `{name}`"),
+            Self::InvalidNamed {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value `{value}` for argument `{name}`: {reason}"),
+            Self::Unexpected { arg } => write!(f, "unexpected argument `{arg}`"),
+        }
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+/// An iterator over the arguments passed to the program.
+///
+/// This iterator is returned by [`Args::iter`]. It implements [`Iterator`], meaning that all the
+/// usual iterator methods (such as [`skip`](Iterator::skip), [`map`](Iterator::map) or
+/// [`collect`](Iterator::collect)) can be chained on it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ftkit::ARGS;
+///
+/// let upper: Vec<String> = ARGS.iter().skip(1).map(str::to_uppercase).collect();
+/// println!("{}", upper.join(" "));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArgsIter<'a> {
+    inner: std::slice::Iter<'a, Box<str>>,
+}
+
+impl<'a> Iterator for ArgsIter<'a> {
+    type Item = &'a str;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(Box::as_ref)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    #[inline(always)]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(Box::as_ref)
+    }
+
+    #[inline(always)]
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.inner.count()
+    }
+}
+
+impl<'a> DoubleEndedIterator for ArgsIter<'a> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(Box::as_ref)
+    }
+
+    #[inline(always)]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(Box::as_ref)
+    }
+}
+
+impl<'a> ExactSizeIterator for ArgsIter<'a> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
 }
 
-unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
-unsafe impl<T: Send> Send for OnceCell<T> {}
+impl<'a> IntoIterator for &'a Args {
+    type Item = &'a str;
+    type IntoIter = ArgsIter<'a>;
 
-impl<T> OnceCell<T> {
-    /// Creates a new [`OnceCell<T>`].
-    pub const fn new() -> Self {
-        Self {
-            value: MaybeUninit::uninit(),
-            state: AtomicU8::new(UNINIT),
-        }
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
+
+/// The arguments passed to the application.
+///
+/// Arguments that are not valid UTF-8 are converted lossily: invalid sequences are replaced with
+/// `U+FFFD REPLACEMENT CHARACTER`. Use [`ARGS_OS`] to access those arguments exactly as they
+/// were passed to the application.
+///
+/// # Extra arguments
+///
+/// When the `FTKIT_ARGS` environment variable is set, its content is split into arguments and
+/// inserted right after the program name, before the arguments passed to the application. This
+/// makes it possible to force options such as `--no-color` across several programs, without
+/// changing the way they are invoked:
+///
+/// ```sh
+/// FTKIT_ARGS="--no-color --seed 42" ./program input.txt
+/// # is the same as
+/// ./program --no-color --seed 42 input.txt
+/// ```
+///
+/// Arguments are separated by whitespace, and may be wrapped in double or single quotes to
+/// include whitespace. [`ARGS_OS`] is not affected.
+///
+/// # Examples
+///
+/// Accessing each argument individually:
+///
+/// ```no_run
+/// use ftkit::ARGS;
+///
+/// println!("{} arguments!", ARGS.len());
+/// println!("name of the process: {}", &ARGS[0]);
+/// ```
+///
+/// Using a `for`-loop:
+///
+/// ```no_run
+/// use ftkit::ARGS;
+///
+/// for arg in ARGS {
+///     println!("{arg}");
+/// }
+/// ```
+///
+/// Taking a range of arguments:
+///
+/// ```no_run
+/// use ftkit::ARGS;
+///
+/// for arg in &ARGS[1..] {
+///     println!("{arg}");
+/// }
+/// ```
+pub static ARGS: &Args = {
+    // The type becomes easier to use when the static itself is a reference. Specifically, it
+    // allows user to do
+    //
+    //     for arg in ARGS
+    //
+    // rather than
+    //
+    //     for arg in &ARGS
+    //
+    // ...which is clearer in my opinion.
+    //
+    // Every method inherent to the `Args` type takes references in any case, so this wont have any
+    // impact on anything.
+    static STORAGE: Args = Args::new();
+    &STORAGE
+};
 
-    /// Returns the value stored in this [`OnceCell<T>`].
+thread_local! {
+    /// The arguments replacing those of the application on the current thread, if any.
     ///
-    /// If the [`OnceCell<T>`] has not been initialized yet, the passed closure is called and its
-    /// return value is used to populate the instance.
-    pub fn get_or_init<F>(&self, f: F) -> &T
-    where
-        F: FnOnce() -> T,
-    {
-        /// In case the `f` function panics, we need to make sure that the previous state is
-        /// properly restored.
-        struct Guard<'a> {
-            /// The state to be restored.
-            state: &'a AtomicU8,
-            /// The state  to be restored.
-            new_state: u8,
-        }
+    /// See [`set_args`].
+    static OVERRIDE: Cell<Option<&'static [Box<str>]>> = const { Cell::new(None) };
+}
 
-        impl<'a> Drop for Guard<'a> {
-            fn drop(&mut self) {
-                // Restore the state.
-                self.state.store(self.new_state, Release);
-            }
-        }
+/// Replaces the arguments seen through [`ARGS`] on the current thread, until the returned guard
+/// is dropped.
+///
+/// This makes it possible to test code that reads [`ARGS`] without spawning a new process. Like
+/// the real arguments, `args` should start with the program name. Because the override only
+/// applies to the current thread, tests running in parallel don't interfere with each other.
+///
+/// The provided arguments are never freed, which is fine for tests but makes this function
+/// unsuitable for use in a loop. [`ARGS_OS`] is not affected.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::ARGS;
+///
+/// fn file_name() -> Option<&'static str> {
+///     ARGS.get(1)
+/// }
+///
+/// let guard = ftkit::set_args(&["prog", "input.txt"]);
+/// assert_eq!(file_name(), Some("input.txt"));
+/// drop(guard);
+/// ```
+pub fn set_args(args: &[&str]) -> ArgsGuard {
+    let args: Box<[Box<str>]> = args.iter().map(|&arg| arg.into()).collect();
+    let previous = OVERRIDE.with(|o| o.replace(Some(Box::leak(args))));
+    ArgsGuard { previous }
+}
 
-        loop {
-            match self
-                .state
-                .compare_exchange_weak(UNINIT, IN_PROGRESS, Acquire, Acquire)
-            {
-                Ok(_) => {
-                    // SAFETY:
-                    //  The state of the cell is currently `IN_PROGRESS`, meaning that we have
-                    //  exclusive access to the value.
-                    let slot = unsafe { &mut *self.value.assume_init_ref().get() };
+/// Restores the arguments seen through [`ARGS`] when dropped.
+///
+/// This guard is returned by [`set_args`].
+#[derive(Debug)]
+#[must_use = "the arguments are restored as soon as the guard is dropped"]
+pub struct ArgsGuard {
+    /// The override that was active before this guard was created.
+    previous: Option<&'static [Box<str>]>,
+}
 
-                    let mut guard = Guard {
-                        state: &self.state,
-                        new_state: UNINIT,
-                    };
+impl Drop for ArgsGuard {
+    fn drop(&mut self) {
+        OVERRIDE.with(|o| o.set(self.previous));
+    }
+}
 
-                    *slot = f();
+/// Tuples whose elements can be parsed from command-line arguments.
+///
+/// This trait is implemented for tuples of up to eight elements, each of which implements
+/// [`FromStr`]. See [`args_as`] for more information.
+pub trait ArgTuple: Sized {
+    /// Parses `args` into a tuple, one argument per element.
+    ///
+    /// Like [`ARGS`], `args` starts with the program name, which is ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::Missing`] if there are not enough arguments,
+    /// [`ArgError::Unexpected`] if there are too many of them, and [`ArgError::Invalid`] if an
+    /// argument can't be parsed into the type of its element.
+    fn parse_tuple(args: &[&str]) -> Result<Self, ArgError>;
+}
 
-                    // The function did not panic! The guard must now mark the value as being
-                    // initialized.
-                    guard.new_state = INIT;
+/// Implements [`ArgTuple`] for tuples of the provided types.
+macro_rules! impl_arg_tuple {
+    ($($($t:ident),+;)*) => {
+        $(
+            impl<$($t),+> ArgTuple for ($($t,)+)
+            where
+                $($t: FromStr, $t::Err: fmt::Display,)+
+            {
+                fn parse_tuple(args: &[&str]) -> Result<Self, ArgError> {
+                    let count = [$(stringify!($t)),+].len();
+                    if let Some(&extra) = args.get(count + 1) {
+                        return Err(ArgError::Unexpected {
+                            arg: extra.to_owned(),
+                        });
+                    }
 
-                    break slot;
-                }
-                Err(INIT) => {
-                    // SAFETY:
-                    //  The value is already initialized. We can simply return a reference to the
-                    //  underlying value.
-                    break unsafe { &*self.value.assume_init_ref().get() };
-                }
-                Err(IN_PROGRESS | UNINIT) => {
-                    // The value is currently being initialized by another thread. We just have to
-                    // retry sometime later. This branch also takes care of spurious fails of
-                    // `compare_exchange_weak`.
-
-                    // NOTE:
-                    //  This is basically a spin-loop. It's not ideal, but it will suffice for our
-                    //  use-case.
-                    std::thread::yield_now();
+                    let mut index = 0;
+                    Ok(($(
+                        {
+                            index += 1;
+                            let value = args.get(index).ok_or(ArgError::Missing { index })?;
+                            parse_at::<$t>(index, value)?
+                        },
+                    )+))
                 }
-                Err(_) => unsafe {
-                    // SAFETY:
-                    //  The `state` can ever only take three values: `INIT`, `IN_PROGRESS` and
-                    //  `INCOMPLETE`.
-                    std::hint::unreachable_unchecked();
-                },
             }
-        }
-    }
+        )*
+    };
 }
 
-/// Represents the arguments passed to the application.
+impl_arg_tuple! {
+    A;
+    A, B;
+    A, B, C;
+    A, B, C, D;
+    A, B, C, D, E;
+    A, B, C, D, E, F;
+    A, B, C, D, E, F, G;
+    A, B, C, D, E, F, G, H;
+}
+
+/// Parses the arguments passed to the application into a tuple.
 ///
-/// See [`ARGS`] more detailed information.
-pub struct Args {
+/// Each argument after the program name is parsed into the corresponding element of the tuple,
+/// which can be of any type implementing [`FromStr`]. The number of arguments must match the
+/// number of elements exactly. A tuple with a single element is written `(T,)`.
+///
+/// # Errors
+///
+/// This function returns [`ArgError::Missing`] if there are not enough arguments,
+/// [`ArgError::Unexpected`] if there are too many of them, and [`ArgError::Invalid`] if an
+/// argument can't be parsed. The error names the position of the faulty argument.
+///
+/// # Examples
+///
+/// ```no_run
+/// // ./rectangle 3 4.5 m
+/// let (width, height, unit) = match ftkit::args_as::<(u32, f64, String)>() {
+///     Ok(args) => args,
+///     Err(err) => ftkit::ARGS.exit_usage(&err.to_string()),
+/// };
+///
+/// println!("area: {} {unit}²", width as f64 * height);
+/// ```
+pub fn args_as<T: ArgTuple>() -> Result<T, ArgError> {
+    T::parse_tuple(&ARGS.as_strs())
+}
+
+/// Represents the arguments passed to the application, as [`OsStr`]s.
+///
+/// See [`ARGS_OS`] for more detailed information.
+pub struct ArgsOs {
     /// The cached arguments.
     ///
     /// The first time those arguments are accessed, this cell is initialized.
-    cache: OnceCell<Box<[Box<str>]>>,
+    cache: OnceCell<Box<[Box<OsStr>]>>,
 }
 
-impl Args {
-    /// Creates a new [`Args`] instance.
+impl ArgsOs {
+    /// Creates a new [`ArgsOs`] instance.
     const fn new() -> Self {
         Self {
             cache: OnceCell::new(),
         }
     }
 
-    /// Forces the cache of this [`Args`] instance to be populated. The content of the now-complete
-    /// cache is returned.
-    fn force(&self) -> &[Box<str>] {
-        self.cache
-            .get_or_init(|| std::env::args().map(String::into_boxed_str).collect())
+    /// Forces the cache of this [`ArgsOs`] instance to be populated. The content of the
+    /// now-complete cache is returned.
+    fn force(&self) -> &[Box<OsStr>] {
+        self.cache.get_or_init(|| {
+            std::env::args_os()
+                .map(OsString::into_boxed_os_str)
+                .collect()
+        })
     }
 
     /// Returns the number of command-line arguments passed to the application.
@@ -149,9 +1702,9 @@ impl Args {
     /// # Examples
     ///
     /// ```no_run
-    /// use ftkit::ARGS;
+    /// use ftkit::ARGS_OS;
     ///
-    /// println!("count: {}", ARGS.len());
+    /// println!("count: {}", ARGS_OS.len());
     /// ```
     #[inline]
     pub fn len(&self) -> usize {
@@ -160,29 +1713,63 @@ impl Args {
 
     /// Returns whether the process was executed without any arguments.
     ///
-    /// Note that this is very unlikely. Even when no concrete arguments are passed to the
-    /// process, its name is still used as the first and only argument.
+    /// See [`Args::is_empty`] for more information.
     ///
     /// ```no_run
-    /// use ftkit::ARGS;
+    /// use ftkit::ARGS_OS;
     ///
-    /// assert!(!ARGS.is_empty());
+    /// assert!(!ARGS_OS.is_empty());
     /// ```
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.force().is_empty()
     }
+
+    /// Returns the argument at position `index`, or `None` if there are not enough arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS_OS;
+    /// use std::path::Path;
+    ///
+    /// if let Some(file) = ARGS_OS.get(1) {
+    ///     println!("size: {}", Path::new(file).metadata().unwrap().len());
+    /// }
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&OsStr> {
+        self.force().get(index).map(Box::as_ref)
+    }
+
+    /// Returns an iterator over the arguments passed to the application.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS_OS;
+    ///
+    /// for file in ARGS_OS.iter().skip(1) {
+    ///     println!("{}", file.to_string_lossy());
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> ArgsOsIter<'_> {
+        ArgsOsIter {
+            inner: self.force().iter(),
+        }
+    }
 }
 
-impl fmt::Debug for Args {
+impl fmt::Debug for ArgsOs {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(self.force(), f)
     }
 }
 
-impl ops::Index<usize> for Args {
-    type Output = str;
+impl ops::Index<usize> for ArgsOs {
+    type Output = OsStr;
 
     #[inline]
     fn index(&self, index: usize) -> &Self::Output {
@@ -190,14 +1777,16 @@ impl ops::Index<usize> for Args {
     }
 }
 
-/// An iterator over the arguments passed to the program.
+/// An iterator over the arguments passed to the program, as [`OsStr`]s.
+///
+/// This iterator is returned by [`ArgsOs::iter`].
 #[derive(Debug, Clone)]
-pub struct ArgsIter<'a> {
-    inner: std::slice::Iter<'a, Box<str>>,
+pub struct ArgsOsIter<'a> {
+    inner: std::slice::Iter<'a, Box<OsStr>>,
 }
 
-impl<'a> Iterator for ArgsIter<'a> {
-    type Item = &'a str;
+impl<'a> Iterator for ArgsOsIter<'a> {
+    type Item = &'a OsStr;
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
@@ -223,7 +1812,7 @@ impl<'a> Iterator for ArgsIter<'a> {
     }
 }
 
-impl<'a> DoubleEndedIterator for ArgsIter<'a> {
+impl<'a> DoubleEndedIterator for ArgsOsIter<'a> {
     #[inline(always)]
     fn next_back(&mut self) -> Option<Self::Item> {
         self.inner.next_back().map(Box::as_ref)
@@ -235,61 +1824,284 @@ impl<'a> DoubleEndedIterator for ArgsIter<'a> {
     }
 }
 
-impl<'a> ExactSizeIterator for ArgsIter<'a> {
+impl<'a> ExactSizeIterator for ArgsOsIter<'a> {
     #[inline(always)]
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a> IntoIterator for &'a Args {
-    type Item = &'a str;
-    type IntoIter = ArgsIter<'a>;
+impl<'a> IntoIterator for &'a ArgsOs {
+    type Item = &'a OsStr;
+    type IntoIter = ArgsOsIter<'a>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        ArgsIter {
-            inner: self.force().iter(),
-        }
+        self.iter()
     }
 }
 
-/// The arguments passed to the application.
-///
-/// # Examples
-///
-/// Accessing each argument individually:
+/// The arguments passed to the application, as [`OsStr`]s.
 ///
-/// ```no_run
-/// use ftkit::ARGS;
-///
-/// println!("{} arguments!", ARGS.len());
-/// println!("name of the process: {}", &ARGS[0]);
-/// ```
+/// [`ARGS`] requires arguments to be valid UTF-8, which is not always the case: file names, in
+/// particular, may contain arbitrary bytes on some platforms. This static provides the same
+/// features as [`ARGS`], but exposes the arguments exactly as they were passed to the
+/// application. They can be converted into [`Path`](std::path::Path)s without any loss.
 ///
-/// Using a `for`-loop:
+/// # Examples
 ///
 /// ```no_run
-/// use ftkit::ARGS;
+/// use ftkit::ARGS_OS;
+/// use std::path::Path;
 ///
-/// for arg in ARGS {
-///     println!("{arg}");
+/// for arg in ARGS_OS.iter().skip(1) {
+///     let path = Path::new(arg);
+///     println!("{}: {}", path.display(), path.exists());
 /// }
 /// ```
-pub static ARGS: &Args = {
-    // The type becomes easier to use when the static itself is a reference. Specifically, it
-    // allows user to do
-    //
-    //     for arg in ARGS
-    //
-    // rather than
-    //
-    //     for arg in &ARGS
-    //
-    // ...which is clearer in my opinion.
-    //
-    // Every method inherent to the `Args` type takes references in any case, so this wont have any
-    // impact on anything.
-    static STORAGE: Args = Args::new();
+pub static ARGS_OS: &ArgsOs = {
+    // See `ARGS` for why the static is a reference.
+    static STORAGE: ArgsOs = ArgsOs::new();
     &STORAGE
 };
+
+#[cfg(test)]
+mod basename {
+    use super::basename;
+
+    #[test]
+    fn strips_directories() {
+        assert_eq!(basename("hello"), "hello");
+        assert_eq!(basename("./target/debug/hello"), "hello");
+        assert_eq!(basename("C:\\Users\\me\\hello.exe"), "hello.exe");
+        assert_eq!(basename("dir\\sub/hello"), "hello");
+    }
+}
+
+#[cfg(test)]
+mod count_flag_in {
+    use super::count_flag_in;
+
+    #[test]
+    fn exact() {
+        assert_eq!(count_flag_in("-v|--verbose", "-v"), 1);
+        assert_eq!(count_flag_in("-v|--verbose", "--verbose"), 1);
+        assert_eq!(count_flag_in("-v|--verbose", "--verbosity"), 0);
+    }
+
+    #[test]
+    fn clusters() {
+        assert_eq!(count_flag_in("-v", "-vvv"), 3);
+        assert_eq!(count_flag_in("-v|--verbose", "-xvy"), 1);
+        assert_eq!(count_flag_in("-v", "-xyz"), 0);
+        assert_eq!(count_flag_in("--v", "-vv"), 0);
+        assert_eq!(count_flag_in("-v", "--vv"), 0);
+        assert_eq!(count_flag_in("-1", "-12"), 0);
+        assert_eq!(count_flag_in("-1", "-1"), 1);
+    }
+}
+
+#[cfg(test)]
+mod arg_tuple {
+    use super::{ArgError, ArgTuple};
+
+    #[test]
+    fn parses_elements() {
+        let args = <(i32, String, f64)>::parse_tuple(&["prog", "-3", "abc", "1.5"]);
+        assert_eq!(args, Ok((-3, "abc".to_owned(), 1.5)));
+    }
+
+    #[test]
+    fn reports_position() {
+        assert_eq!(
+            <(i32, i32)>::parse_tuple(&["prog", "1"]),
+            Err(ArgError::Missing { index: 2 })
+        );
+        assert_eq!(
+            <(i32,)>::parse_tuple(&["prog", "1", "2"]),
+            Err(ArgError::Unexpected { arg: "2".into() })
+        );
+        assert!(matches!(
+            <(i32, u8)>::parse_tuple(&["prog", "1", "300"]),
+            Err(ArgError::Invalid { index: 2, .. })
+        ));
+    }
+}
+
+#[cfg(test)]
+mod split_quoted {
+    use super::{expand_response_files, split_quoted};
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(
+            split_quoted("--width 80\n\t--height  24\r\n"),
+            ["--width", "80", "--height", "24"]
+        );
+        assert!(split_quoted(" \n ").is_empty());
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            split_quoted(r#"--name "John Doe" 'it''s' """#),
+            ["--name", "John Doe", "its", ""]
+        );
+    }
+
+    #[test]
+    fn separator() {
+        let path = std::env::temp_dir().join(format!("ftkit-response-{}.txt", std::process::id()));
+        std::fs::write(&path, "-v 'a b'").unwrap();
+        let arg = format!("@{}", path.display());
+
+        let args = ["prog", &arg, "--", &arg].map(String::from);
+        let expanded = expand_response_files(args);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(expanded, ["prog", "-v", "a b", "--", &arg]);
+    }
+}
+
+#[cfg(test)]
+mod describe_count {
+    use super::describe_count;
+    use std::ops::RangeBounds;
+
+    fn describe(range: impl RangeBounds<usize>) -> String {
+        describe_count(range.start_bound(), range.end_bound())
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(describe(1..=1), "1 argument");
+        assert_eq!(describe(2..3), "2 arguments");
+        assert_eq!(describe(1..), "at least 1 argument");
+        assert_eq!(describe(..=3), "at most 3 arguments");
+        assert_eq!(describe(1..4), "between 1 and 3 arguments");
+    }
+}
+
+#[cfg(test)]
+mod override_args {
+    use super::{set_args, ARGS};
+
+    #[test]
+    fn scoped() {
+        let real = ARGS.len();
+        {
+            let _guard = set_args(&["prog", "-v", "input.txt"]);
+            assert_eq!(ARGS.program_name(), "prog");
+            assert!(ARGS.flag("-v"));
+            assert_eq!(ARGS.positional().collect::<Vec<_>>(), ["input.txt"]);
+
+            let _inner = set_args(&["other"]);
+            assert_eq!(ARGS.len(), 1);
+        }
+        assert_eq!(ARGS.len(), real);
+    }
+}
+
+#[cfg(test)]
+mod glob_match {
+    use super::glob_match;
+
+    #[test]
+    fn wildcards() {
+        assert!(glob_match("*.txt", "notes.txt"));
+        assert!(!glob_match("*.txt", ".txt.txt"));
+        assert!(glob_match("a?c*", "abcdef"));
+        assert!(glob_match("*a*b", "xaab"));
+        assert!(!glob_match("*.txt", "notes.txt.bak"));
+        assert!(!glob_match("?", ""));
+        assert!(glob_match(".*", ".hidden"));
+        assert!(glob_match("é?", "éa"));
+    }
+}
+
+#[cfg(test)]
+mod is_positional {
+    use super::is_positional;
+
+    #[test]
+    fn numbers() {
+        for arg in ["-5", "-0.5", "-.5", "-1e3", "42", "-"] {
+            assert!(is_positional(arg), "{arg}");
+        }
+        for arg in ["-v", "--5", "-inf", "-nan", "-5x", "--"] {
+            assert!(!is_positional(arg), "{arg}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod parsed_arg {
+    use super::{set_args, ParsedArg::*, ARGS};
+
+    #[test]
+    fn classification() {
+        let _guard = set_args(&["prog", "-la", "--width=80", "in", "-", "-5", "--", "-v"]);
+        assert_eq!(
+            ARGS.parsed().collect::<Vec<_>>(),
+            [
+                Flag("-la"),
+                Option("--width", "80"),
+                Positional("in"),
+                Positional("-"),
+                Positional("-5"),
+                Positional("-v"),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod paths {
+    use super::{set_args, ArgError, ARGS};
+
+    #[test]
+    fn existing() {
+        let _guard = set_args(&["prog", "Cargo.toml", "missing.txt"]);
+        assert_eq!(
+            ARGS.existing_path(1).unwrap(),
+            std::path::Path::new("Cargo.toml")
+        );
+        assert!(ARGS.canonical_path(1).unwrap().is_absolute());
+        assert_eq!(
+            ARGS.existing_path(2),
+            Err(ArgError::Invalid {
+                index: 2,
+                value: "missing.txt".into(),
+                reason: "no such file or directory".into(),
+            })
+        );
+        assert!(ARGS.canonical_path(2).is_err());
+        assert_eq!(ARGS.path(3), Err(ArgError::Missing { index: 3 }));
+    }
+}
+
+#[cfg(test)]
+mod option_values {
+    use super::{set_args, ArgError, ARGS};
+
+    #[test]
+    fn flag_is_not_a_value() {
+        let _guard = set_args(&["prog", "--name", "--verbose"]);
+        assert_eq!(ARGS.option("--name"), None);
+        assert!(ARGS.flag("--verbose"));
+        assert_eq!(
+            ARGS.option_parse::<String>("--name"),
+            Err(ArgError::MissingValue {
+                option: "--name".into()
+            })
+        );
+    }
+
+    #[test]
+    fn positional_values() {
+        let _guard = set_args(&["prog", "--offset", "-5", "--name", "-", "--last", "--", "x"]);
+        assert_eq!(ARGS.option("--offset"), Some("-5"));
+        assert_eq!(ARGS.option("--name"), Some("-"));
+        assert_eq!(ARGS.option("--last"), None);
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{preferred_name, ArgError, Args, ARGS};
+
+/// Types that can be built from the arguments passed to the application.
+///
+/// Implementing this trait for a configuration structure keeps all of the argument handling of a
+/// program in one place. The structure can then be obtained with [`parse_args`], which reports
+/// errors and exits the application when the arguments are invalid.
+///
+/// The methods of [`Args`] that return a [`Result`], such as [`Args::parse_positional`],
+/// [`Args::require_option`] and [`Args::option_or_parse`], are designed to be chained with `?`
+/// within [`from_args`](Self::from_args).
+///
+/// Because there is no way to know which options expect a value, the value of an option written
+/// as `--name value` is counted as a positional argument (see [`Args::positional`]). Programs
+/// that mix options and positional arguments should expect options written as `--name=value`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ftkit::{ArgError, Args, FromArgs};
+///
+/// // ./resize --width=640 photo.png --keep-ratio
+/// struct Config {
+///     file: String,
+///     width: u32,
+///     height: Option<u32>,
+///     keep_ratio: bool,
+/// }
+///
+/// impl FromArgs for Config {
+///     fn from_args(args: &Args) -> Result<Self, ArgError> {
+///         Ok(Config {
+///             file: args.parse_positional(0, "file")?,
+///             width: args.require_option("-w|--width")?,
+///             height: args.option_parse("--height")?,
+///             keep_ratio: args.flag("--keep-ratio"),
+///         })
+///     }
+/// }
+///
+/// let config: Config = ftkit::parse_args();
+/// println!("resizing {} to {} pixels", config.file, config.width);
+/// ```
+pub trait FromArgs: Sized {
+    /// Builds an instance of this type from `args`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`ArgError`] describing the first invalid or missing argument.
+    fn from_args(args: &Args) -> Result<Self, ArgError>;
+}
+
+/// Builds a `T` from the arguments passed to the application, exiting the application if they
+/// are invalid.
+///
+/// When [`FromArgs::from_args`] fails, the error and the usage message are printed to the
+/// standard error, and the application exits with status `2` (see
+/// [`Args::exit_usage`](crate::Args::exit_usage)).
+///
+/// # Examples
+///
+/// ```no_run
+/// use ftkit::{ArgError, Args, FromArgs};
+///
+/// // ./greet --times=3 Bob
+/// struct Greeting {
+///     name: String,
+///     times: usize,
+/// }
+///
+/// impl FromArgs for Greeting {
+///     fn from_args(args: &Args) -> Result<Self, ArgError> {
+///         Ok(Greeting {
+///             name: args.parse_positional(0, "name")?,
+///             times: args.option_or_parse("-n|--times", 1)?,
+///         })
+///     }
+/// }
+///
+/// let greeting: Greeting = ftkit::parse_args();
+/// for _ in 0..greeting.times {
+///     println!("Hello, {}!", greeting.name);
+/// }
+/// ```
+pub fn parse_args<T: FromArgs>() -> T {
+    match T::from_args(ARGS) {
+        Ok(value) => value,
+        Err(err) => ARGS.exit_usage(&err.to_string()),
+    }
+}
+
+impl Args {
+    /// Parses the positional argument at position `n` into a value of type `T`.
+    ///
+    /// Positions are counted among the positional arguments only (see
+    /// [`positional`](Self::positional)), starting at `0`, so flags may appear anywhere.
+    /// `name` is only used in error messages.
+    ///
+    /// Note that the value of an option written as `--name value` is counted as a positional
+    /// argument, which shifts the positions of the arguments that follow it. Options used along
+    /// with this function should be written as `--name=value`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::MissingNamed`] if there are not enough positional
+    /// arguments, and [`ArgError::InvalidNamed`] if the argument can't be parsed into a `T`.
+    /// When an option is written as `--name value`, the error may be about the wrong argument.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./repeat --verbose hello 3
+    /// let word: String = ARGS.parse_positional(0, "word").unwrap();
+    /// let times: usize = ARGS.parse_positional(1, "times").unwrap();
+    /// println!("{}", word.repeat(times));
+    /// ```
+    pub fn parse_positional<T>(&self, n: usize, name: &str) -> Result<T, ArgError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self
+            .positional()
+            .nth(n)
+            .ok_or_else(|| ArgError::MissingNamed {
+                name: name.to_owned(),
+            })?;
+
+        value.parse().map_err(|err: T::Err| ArgError::InvalidNamed {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Parses the value of the option `name` into a value of type `T`, failing if the option
+    /// was not passed to the application.
+    ///
+    /// This is the same as [`option_parse`](Self::option_parse), for options that are
+    /// mandatory.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::MissingNamed`] if the option was not passed to the
+    /// application, in addition to the errors of [`option_parse`](Self::option_parse). The error
+    /// names the option by its long spelling, such as `--output` for `"-o|--output"`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ARGS;
+    ///
+    /// // ./server --port 8080
+    /// let port: u16 = match ARGS.require_option("--port") {
+    ///     Ok(port) => port,
+    ///     Err(err) => ARGS.exit_usage(&err.to_string()),
+    /// };
+    /// ```
+    pub fn require_option<T>(&self, name: &str) -> Result<T, ArgError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.option_parse(name)?
+            .ok_or_else(|| ArgError::MissingNamed {
+                name: preferred_name(name).to_owned(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod config {
+    use super::FromArgs;
+    use crate::{set_args, ArgError, Args, ARGS};
+
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        input: String,
+        count: i32,
+        width: u32,
+        verbose: bool,
+    }
+
+    impl FromArgs for Config {
+        fn from_args(args: &Args) -> Result<Self, ArgError> {
+            Ok(Config {
+                input: args.parse_positional(0, "input")?,
+                count: args.parse_positional(1, "count")?,
+                width: args.require_option("-w|--width")?,
+                verbose: args.flag("-v"),
+            })
+        }
+    }
+
+    #[test]
+    fn valid() {
+        let _guard = set_args(&["prog", "-v", "in.txt", "--width=80", "-3"]);
+        assert_eq!(
+            Config::from_args(ARGS),
+            Ok(Config {
+                input: "in.txt".into(),
+                count: -3,
+                width: 80,
+                verbose: true,
+            })
+        );
+    }
+
+    #[test]
+    fn option_before_positionals() {
+        let _guard = set_args(&["prog", "--width=80", "-v", "in.txt", "3"]);
+        assert_eq!(
+            Config::from_args(ARGS),
+            Ok(Config {
+                input: "in.txt".into(),
+                count: 3,
+                width: 80,
+                verbose: true,
+            })
+        );
+
+        // The value of an option written as `--name value` is positional.
+        let _guard = set_args(&["prog", "-w", "80", "in.txt", "3"]);
+        assert!(matches!(
+            Config::from_args(ARGS),
+            Err(ArgError::InvalidNamed { name, .. }) if name == "count"
+        ));
+    }
+
+    #[test]
+    fn invalid() {
+        let _guard = set_args(&["prog", "in.txt", "three"]);
+        assert!(matches!(
+            Config::from_args(ARGS),
+            Err(ArgError::InvalidNamed { name, .. }) if name == "count"
+        ));
+
+        let _guard = set_args(&["prog", "in.txt", "3"]);
+        assert_eq!(
+            Config::from_args(ARGS),
+            Err(ArgError::MissingNamed {
+                name: "--width".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,932 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use super::{is_positional, matches_name, ArgError, ARGS};
+use crate::ENV;
+
+mod completion;
+pub use self::completion::*;
+
+/// The width of help messages when the width of the terminal is unknown.
+const DEFAULT_WIDTH: usize = 80;
+
+/// The maximum width of the column listing argument names in help messages.
+///
+/// Descriptions of longer names start on the next line.
+const MAX_NAME_WIDTH: usize = 24;
+
+/// Returns whether the `|`-separated spellings of `a` and `b` have a spelling in common.
+fn same_name(a: &str, b: &str) -> bool {
+    b.split('|').any(|spelling| matches_name(a, spelling))
+}
+
+/// Formats the `|`-separated spellings of `name` for help messages.
+fn display_name(name: &str) -> String {
+    name.split('|').collect::<Vec<_>>().join(", ")
+}
+
+/// Returns the width of the terminal, as advertised by the `COLUMNS` environment variable.
+fn terminal_width() -> usize {
+    ENV.get("COLUMNS")
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Splits `text` into lines of at most `width` characters, breaking at whitespace.
+///
+/// Words longer than `width` are put on their own line.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Writes a section of a help message, such as the list of options.
+///
+/// Each entry is a name and its description. Descriptions are aligned in a column and wrapped to
+/// fit within `width` characters.
+fn write_section(help: &mut String, title: &str, entries: &[(String, String)], width: usize) {
+    let _ = writeln!(help, "\n{title}:");
+
+    let name_width = entries
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .filter(|&w| w <= MAX_NAME_WIDTH)
+        .max()
+        .unwrap_or(0);
+    // Two spaces of indentation, and two spaces between the names and their descriptions.
+    let indent = name_width + 4;
+    let description_width = width.saturating_sub(indent).max(20);
+
+    for (name, description) in entries {
+        let mut lines = wrap(description, description_width).into_iter();
+        let _ = write!(help, "  {name}");
+        if name.chars().count() > name_width {
+            help.push('\n');
+        } else if let Some(first) = lines.next() {
+            let _ = write!(
+                help,
+                "{:pad$}{first}",
+                "",
+                pad = indent - 2 - name.chars().count()
+            );
+        }
+        if !help.ends_with('\n') {
+            help.push('\n');
+        }
+        for line in lines {
+            let _ = writeln!(help, "{:indent$}{line}", "");
+        }
+    }
+}
+
+/// A positional argument declared in an [`ArgSpec`].
+#[derive(Debug, Clone)]
+struct Positional {
+    /// The name of the argument.
+    name: String,
+    /// Whether the argument must be provided.
+    required: bool,
+    /// The description of the argument, printed in help messages.
+    description: String,
+}
+
+/// A flag declared in an [`ArgSpec`].
+#[derive(Debug, Clone)]
+struct Flag {
+    /// The `|`-separated spellings of the flag.
+    name: String,
+    /// The description of the flag, printed in help messages.
+    description: String,
+}
+
+/// An option declared in an [`ArgSpec`].
+#[derive(Debug, Clone)]
+struct Opt {
+    /// The `|`-separated spellings of the option.
+    name: String,
+    /// The value of the option when it is not provided.
+    default: String,
+    /// The description of the option, printed in help messages.
+    description: String,
+}
+
+/// The kinds of arguments that can be declared in an [`ArgSpec`].
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Positional,
+    Flag,
+    Opt,
+}
+
+/// A description of the arguments expected by an application.
+///
+/// An [`ArgSpec`] lists the positional arguments, flags and options of a program. It can then
+/// parse the arguments passed to the application, check that they match the description, and
+/// generate a help message for the `-h` and `--help` flags.
+///
+/// Like with [`Args::flag`](crate::Args::flag), flags and options may have several spellings,
+/// separated by `|`, such as `"-v|--verbose"`.
+///
+/// Single-character flags may be grouped together, as in `-la`. Arguments that follow the `--`
+/// separator are always positional, and so are negative numbers such as `-5`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ftkit::ArgSpec;
+///
+/// // ./head --lines 5 notes.txt
+/// let args = ArgSpec::new()
+///     .about("Prints the first lines of a file.")
+///     .positional("file")
+///     .describe("The file to read.")
+///     .flag("-v|--verbose")
+///     .option("-n|--lines", 10)
+///     .describe("The number of lines to print.")
+///     .parse();
+///
+/// let file = args.get("file").unwrap();
+/// let lines: usize = args.value("--lines");
+/// if args.flag("--verbose") {
+///     println!("Printing {lines} lines of {file}.");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ArgSpec {
+    /// A short description of the application.
+    about: Option<String>,
+    /// The positional arguments, in order.
+    positionals: Vec<Positional>,
+    /// The flags.
+    flags: Vec<Flag>,
+    /// The options.
+    options: Vec<Opt>,
+    /// The kind of the argument declared last, which [`ArgSpec::describe`] applies to.
+    last: Option<Kind>,
+}
+
+impl ArgSpec {
+    /// Creates a new [`ArgSpec`] that expects no arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// // Fails if any argument is passed to the application.
+    /// ArgSpec::new().parse();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the description of the application, printed at the top of the help message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// ArgSpec::new().about("Says hello.").parse();
+    /// ```
+    pub fn about(mut self, about: &str) -> Self {
+        self.about = Some(about.to_owned());
+        self
+    }
+
+    /// Adds a required positional argument named `name`.
+    ///
+    /// Positional arguments are filled in the order in which they are declared.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if an optional positional argument was declared before.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new().positional("source").positional("destination").parse();
+    /// println!("{} -> {}", args.get("source").unwrap(), args.get("destination").unwrap());
+    /// ```
+    pub fn positional(mut self, name: &str) -> Self {
+        assert!(
+            self.positionals.iter().all(|p| p.required),
+            "required positional arguments must be declared before optional ones"
+        );
+
+        self.positionals.push(Positional {
+            name: name.to_owned(),
+            required: true,
+            description: String::new(),
+        });
+        self.last = Some(Kind::Positional);
+        self
+    }
+
+    /// Adds an optional positional argument named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new().optional("name").parse();
+    /// println!("Hello, {}!", args.get("name").unwrap_or("stranger"));
+    /// ```
+    pub fn optional(mut self, name: &str) -> Self {
+        self.positionals.push(Positional {
+            name: name.to_owned(),
+            required: false,
+            description: String::new(),
+        });
+        self.last = Some(Kind::Positional);
+        self
+    }
+
+    /// Adds a flag, which is either present or not.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new().flag("-q|--quiet").parse();
+    /// if !args.flag("--quiet") {
+    ///     println!("Hello!");
+    /// }
+    /// ```
+    pub fn flag(mut self, name: &str) -> Self {
+        self.flags.push(Flag {
+            name: name.to_owned(),
+            description: String::new(),
+        });
+        self.last = Some(Kind::Flag);
+        self
+    }
+
+    /// Adds an option, which takes a value.
+    ///
+    /// The value may be provided either as `--name value` or as `--name=value`. In the first
+    /// form, a value starting with `-` must be a negative number, as flags and the `--`
+    /// separator are never taken as values. When the option is not provided, `default` is used
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new().option("--greeting", "Hello").parse();
+    /// println!("{}, world!", args.get("--greeting").unwrap());
+    /// ```
+    pub fn option(mut self, name: &str, default: impl fmt::Display) -> Self {
+        self.options.push(Opt {
+            name: name.to_owned(),
+            default: default.to_string(),
+            description: String::new(),
+        });
+        self.last = Some(Kind::Opt);
+        self
+    }
+
+    /// Sets the description of the argument declared last, printed in the help message.
+    ///
+    /// Long descriptions are wrapped to fit the width of the terminal.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no argument was declared yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new()
+    ///     .positional("name")
+    ///     .describe("The name of the person to greet.")
+    ///     .flag("-s|--shout")
+    ///     .describe("Greets the person loudly.")
+    ///     .parse();
+    /// ```
+    pub fn describe(mut self, description: &str) -> Self {
+        let slot = match self.last {
+            Some(Kind::Positional) => self.positionals.last_mut().map(|p| &mut p.description),
+            Some(Kind::Flag) => self.flags.last_mut().map(|f| &mut f.description),
+            Some(Kind::Opt) => self.options.last_mut().map(|o| &mut o.description),
+            None => None,
+        };
+        match slot {
+            Some(slot) => *slot = description.to_owned(),
+            None => panic!("`describe` must be called after an argument was declared"),
+        }
+        self
+    }
+
+    /// Returns the part of the usage message that follows the program name.
+    fn usage_spec(&self) -> String {
+        let mut spec = String::from("[options]");
+        for p in &self.positionals {
+            if p.required {
+                let _ = write!(spec, " <{}>", p.name);
+            } else {
+                let _ = write!(spec, " [{}]", p.name);
+            }
+        }
+        spec
+    }
+
+    /// Returns the help message described by this [`ArgSpec`].
+    ///
+    /// This is the message printed when the application receives the `-h` or `--help` flag. It
+    /// includes the usage line, then lists the positional arguments and options with their
+    /// descriptions (see [`describe`](Self::describe)). Descriptions are aligned and wrapped to
+    /// the width of the terminal, as advertised by the `COLUMNS` environment variable, or to 80
+    /// characters when it is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let spec = ArgSpec::new()
+    ///     .positional("file")
+    ///     .flag("-v|--verbose")
+    ///     .describe("Prints more information.");
+    /// println!("{}", spec.help());
+    /// ```
+    pub fn help(&self) -> String {
+        self.help_with_width(terminal_width())
+    }
+
+    /// Returns the help message described by this [`ArgSpec`], wrapped to `width` characters.
+    fn help_with_width(&self, width: usize) -> String {
+        let mut help = String::new();
+        if let Some(about) = &self.about {
+            for line in wrap(about, width) {
+                let _ = writeln!(help, "{line}");
+            }
+            help.push('\n');
+        }
+        let _ = writeln!(help, "usage: {} {}", ARGS.program_name(), self.usage_spec());
+
+        if !self.positionals.is_empty() {
+            let entries: Vec<_> = self
+                .positionals
+                .iter()
+                .map(|p| (p.name.clone(), p.description.clone()))
+                .collect();
+            write_section(&mut help, "arguments", &entries, width);
+        }
+
+        let mut entries: Vec<_> = self
+            .flags
+            .iter()
+            .map(|f| (display_name(&f.name), f.description.clone()))
+            .collect();
+        entries.extend(self.options.iter().map(|o| {
+            let description = if o.description.is_empty() {
+                format!("(default: {})", o.default)
+            } else {
+                format!("{} (default: {})", o.description, o.default)
+            };
+            (format!("{} <value>", display_name(&o.name)), description)
+        }));
+        entries.push(("-h, --help".into(), "Prints this help message.".into()));
+        write_section(&mut help, "options", &entries, width);
+        help
+    }
+
+    /// Interprets `arg` as a cluster of short flags, such as `-la`.
+    ///
+    /// The positions of the flags in the cluster are returned, or `None` if `arg` is not a
+    /// cluster of declared flags.
+    fn flag_cluster(&self, arg: &str) -> Option<Vec<usize>> {
+        let cluster = arg.strip_prefix('-')?;
+        if cluster.starts_with('-') {
+            return None;
+        }
+
+        let mut buf = [0; 4];
+        cluster
+            .chars()
+            .map(|c| {
+                let flag = ["-", c.encode_utf8(&mut buf)].concat();
+                self.flags.iter().position(|f| matches_name(&f.name, &flag))
+            })
+            .collect()
+    }
+
+    /// Parses `args` according to this [`ArgSpec`].
+    ///
+    /// Unlike [`parse`](Self::parse), this function does not use [`ARGS`], and `args` must not
+    /// include the program name. The `-h` and `--help` flags are not handled specially.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a required positional argument is missing, if an option has no
+    /// value, or if an argument was not declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::{ArgError, ArgSpec};
+    ///
+    /// let spec = ArgSpec::new().positional("file").flag("-v");
+    ///
+    /// let args = spec.try_parse_from(["-v", "notes.txt"]).unwrap();
+    /// assert_eq!(args.get("file"), Some("notes.txt"));
+    /// assert!(args.flag("-v"));
+    ///
+    /// assert!(spec.try_parse_from(["-v"]).is_err());
+    /// ```
+    pub fn try_parse_from<'a>(
+        &self,
+        args: impl IntoIterator<Item = &'a str>,
+    ) -> Result<ArgMatches, ArgError> {
+        let matches = self.parse_tokens(args, None)?;
+        Ok(matches.expect("help is only requested when a help flag is provided"))
+    }
+
+    /// Parses `args` according to this [`ArgSpec`].
+    ///
+    /// When `help` is the position of a flag, `None` is returned as soon as that flag is found,
+    /// before the missing arguments are checked.
+    fn parse_tokens<'a>(
+        &self,
+        args: impl IntoIterator<Item = &'a str>,
+        help: Option<usize>,
+    ) -> Result<Option<ArgMatches>, ArgError> {
+        let mut positionals = Vec::new();
+        let mut flags = vec![false; self.flags.len()];
+        let mut options: Vec<Option<String>> = vec![None; self.options.len()];
+
+        let mut args = args.into_iter().peekable();
+        let mut only_positionals = false;
+        while let Some(arg) = args.next() {
+            if !only_positionals && arg == "--" {
+                only_positionals = true;
+            } else if only_positionals || is_positional(arg) {
+                if positionals.len() == self.positionals.len() {
+                    return Err(ArgError::Unexpected {
+                        arg: arg.to_owned(),
+                    });
+                }
+                positionals.push(arg.to_owned());
+            } else if let Some(i) = self.flags.iter().position(|f| matches_name(&f.name, arg)) {
+                if help == Some(i) {
+                    return Ok(None);
+                }
+                flags[i] = true;
+            } else if let Some(i) = self.options.iter().position(|o| matches_name(&o.name, arg)) {
+                // Flags and the `--` separator are never taken as values.
+                let value = args.next_if(|next| is_positional(next)).ok_or_else(|| {
+                    ArgError::MissingValue {
+                        option: arg.to_owned(),
+                    }
+                })?;
+                options[i] = Some(value.to_owned());
+            } else if let Some((i, value)) = arg.split_once('=').and_then(|(key, value)| {
+                let i = self
+                    .options
+                    .iter()
+                    .position(|o| matches_name(&o.name, key))?;
+                Some((i, value))
+            }) {
+                options[i] = Some(value.to_owned());
+            } else if let Some(cluster) = self.flag_cluster(arg) {
+                if cluster.iter().any(|&i| help == Some(i)) {
+                    return Ok(None);
+                }
+                for i in cluster {
+                    flags[i] = true;
+                }
+            } else {
+                return Err(ArgError::Unexpected {
+                    arg: arg.to_owned(),
+                });
+            }
+        }
+
+        if let Some(missing) = self.positionals.get(positionals.len()) {
+            if missing.required {
+                return Err(ArgError::MissingNamed {
+                    name: missing.name.clone(),
+                });
+            }
+        }
+
+        Ok(Some(ArgMatches {
+            positionals: self
+                .positionals
+                .iter()
+                .map(|p| p.name.clone())
+                .zip(
+                    positionals
+                        .into_iter()
+                        .map(Some)
+                        .chain(std::iter::repeat(None)),
+                )
+                .collect(),
+            flags: self
+                .flags
+                .iter()
+                .map(|f| f.name.clone())
+                .zip(flags)
+                .collect(),
+            options: self
+                .options
+                .iter()
+                .zip(options)
+                .map(|(o, value)| (o.name.clone(), value.unwrap_or_else(|| o.default.clone())))
+                .collect(),
+        }))
+    }
+
+    /// Returns a copy of this [`ArgSpec`] with the `-h|--help` flag declared last, along with
+    /// the position of that flag.
+    fn with_help_flag(&self) -> (Self, usize) {
+        let spec = self.clone().flag("-h|--help");
+        let help = spec.flags.len() - 1;
+        (spec, help)
+    }
+
+    /// Parses the arguments passed to the application according to this [`ArgSpec`].
+    ///
+    /// When the `-h` or `--help` flag is passed to the application, the help message is printed
+    /// and the application exits successfully. Those flags are recognized like the declared
+    /// ones, so `-h` may appear in a cluster of short flags, but not after the `--` separator.
+    /// When `-h` or `--help` is declared with [`flag`](Self::flag), the declared flag takes
+    /// precedence. When the arguments do not match this
+    /// [`ArgSpec`], the error and the usage message are printed to the standard error, and the
+    /// application exits with status `2` (see [`Args::exit_usage`](crate::Args::exit_usage)).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new().positional("name").parse();
+    /// println!("Hello, {}!", args.get("name").unwrap());
+    /// ```
+    pub fn parse(&self) -> ArgMatches {
+        ARGS.set_usage(&self.usage_spec());
+
+        let (spec, help) = self.with_help_flag();
+        match spec.parse_tokens(ARGS.iter().skip(1), Some(help)) {
+            Ok(Some(matches)) => matches,
+            Ok(None) => {
+                print!("{}", self.help());
+                std::process::exit(0);
+            }
+            Err(err) => ARGS.exit_usage(&err.to_string()),
+        }
+    }
+}
+
+/// The arguments parsed by an [`ArgSpec`].
+///
+/// Arguments are looked up by the name they were declared with. For flags and options, any of
+/// their spellings may be used.
+#[derive(Debug, Clone)]
+pub struct ArgMatches {
+    /// The positional arguments, with their names.
+    positionals: Vec<(String, Option<String>)>,
+    /// The flags, with whether they were provided.
+    flags: Vec<(String, bool)>,
+    /// The options, with their values.
+    options: Vec<(String, String)>,
+}
+
+impl ArgMatches {
+    /// Returns the value of the positional argument or option `name`.
+    ///
+    /// `None` is returned for optional positional arguments that were not provided. Options
+    /// always have a value, as they fall back to their default.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no positional argument or option named `name` was declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::ArgSpec;
+    ///
+    /// let spec = ArgSpec::new().positional("file").option("-o|--output", "out.txt");
+    /// let args = spec.try_parse_from(["in.txt"]).unwrap();
+    ///
+    /// assert_eq!(args.get("file"), Some("in.txt"));
+    /// assert_eq!(args.get("-o"), Some("out.txt"));
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        if let Some((_, value)) = self.positionals.iter().find(|(n, _)| n == name) {
+            return value.as_deref();
+        }
+
+        match self.options.iter().find(|(n, _)| same_name(n, name)) {
+            Some((_, value)) => Some(value),
+            None => panic!("no argument named `{name}` was declared"),
+        }
+    }
+
+    /// Returns whether the flag `name` was provided.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no flag named `name` was declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::ArgSpec;
+    ///
+    /// let spec = ArgSpec::new().flag("-v|--verbose");
+    /// assert!(spec.try_parse_from(["-v"]).unwrap().flag("--verbose"));
+    /// assert!(!spec.try_parse_from([]).unwrap().flag("--verbose"));
+    /// ```
+    pub fn flag(&self, name: &str) -> bool {
+        match self.flags.iter().find(|(n, _)| same_name(n, name)) {
+            Some(&(_, present)) => present,
+            None => panic!("no flag named `{name}` was declared"),
+        }
+    }
+
+    /// Parses the value of the positional argument or option `name` into a value of type `T`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ArgError::MissingNamed`] if `name` is an optional positional
+    /// argument that was not provided, and [`ArgError::InvalidNamed`] if the value can't be
+    /// parsed into a `T`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no positional argument or option named `name` was declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::ArgSpec;
+    ///
+    /// let spec = ArgSpec::new().option("--count", 3);
+    ///
+    /// let args = spec.try_parse_from(["--count=5"]).unwrap();
+    /// assert_eq!(args.try_value::<u32>("--count"), Ok(5));
+    ///
+    /// let args = spec.try_parse_from(["--count", "many"]).unwrap();
+    /// assert!(args.try_value::<u32>("--count").is_err());
+    /// ```
+    pub fn try_value<T>(&self, name: &str) -> Result<T, ArgError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.get(name).ok_or_else(|| ArgError::MissingNamed {
+            name: name.to_owned(),
+        })?;
+
+        value.parse().map_err(|err: T::Err| ArgError::InvalidNamed {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Parses the value of the positional argument or option `name` into a value of type `T`,
+    /// exiting the application if it is invalid.
+    ///
+    /// This is the same as [`try_value`](Self::try_value), except that errors are reported like
+    /// in [`ArgSpec::parse`]: the error and the usage message are printed to the standard
+    /// error, and the application exits with status `2`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no positional argument or option named `name` was declared.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ArgSpec;
+    ///
+    /// let args = ArgSpec::new().positional("n").parse();
+    /// let n: u64 = args.value("n");
+    /// println!("{n}! = {}", (1..=n).product::<u64>());
+    /// ```
+    pub fn value<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match self.try_value(name) {
+            Ok(value) => value,
+            Err(err) => ARGS.exit_usage(&err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod try_parse_from {
+    use super::ArgSpec;
+    use crate::ArgError;
+
+    fn spec() -> ArgSpec {
+        ArgSpec::new()
+            .positional("input")
+            .optional("output")
+            .flag("-v|--verbose")
+            .option("-n|--count", 1)
+    }
+
+    #[test]
+    fn all_arguments() {
+        let args = spec()
+            .try_parse_from(["-v", "in", "--count", "3", "out"])
+            .unwrap();
+        assert_eq!(args.get("input"), Some("in"));
+        assert_eq!(args.get("output"), Some("out"));
+        assert!(args.flag("-v"));
+        assert_eq!(args.try_value("--count"), Ok(3));
+    }
+
+    #[test]
+    fn defaults() {
+        let args = spec().try_parse_from(["in"]).unwrap();
+        assert_eq!(args.get("output"), None);
+        assert!(!args.flag("--verbose"));
+        assert_eq!(args.try_value("-n"), Ok(1));
+        assert!(matches!(
+            args.try_value::<String>("output"),
+            Err(ArgError::MissingNamed { .. })
+        ));
+    }
+
+    #[test]
+    fn clusters() {
+        let spec = ArgSpec::new().flag("-a").flag("-l").flag("-x");
+        let args = spec.try_parse_from(["-la"]).unwrap();
+        assert!(args.flag("-l") && args.flag("-a") && !args.flag("-x"));
+
+        assert_eq!(
+            spec.try_parse_from(["-lz"]).unwrap_err(),
+            ArgError::Unexpected { arg: "-lz".into() }
+        );
+    }
+
+    #[test]
+    fn separator() {
+        let args = spec().try_parse_from(["--", "-v", "--"]).unwrap();
+        assert_eq!(args.get("input"), Some("-v"));
+        assert_eq!(args.get("output"), Some("--"));
+        assert!(!args.flag("-v"));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            spec().try_parse_from([]).unwrap_err(),
+            ArgError::MissingNamed {
+                name: "input".into()
+            }
+        );
+        assert_eq!(
+            spec().try_parse_from(["in", "out", "extra"]).unwrap_err(),
+            ArgError::Unexpected {
+                arg: "extra".into()
+            }
+        );
+        assert_eq!(
+            spec().try_parse_from(["in", "--unknown"]).unwrap_err(),
+            ArgError::Unexpected {
+                arg: "--unknown".into()
+            }
+        );
+        assert_eq!(
+            spec().try_parse_from(["in", "-n"]).unwrap_err(),
+            ArgError::MissingValue {
+                option: "-n".into()
+            }
+        );
+    }
+
+    #[test]
+    fn negative_numbers() {
+        let spec = ArgSpec::new()
+            .positional("a")
+            .positional("b")
+            .flag("-v")
+            .option("-n", 0);
+        let args = spec
+            .try_parse_from(["-3", "-v", "-n", "-2", "-0.5"])
+            .unwrap();
+        assert_eq!(args.try_value("a"), Ok(-3));
+        assert_eq!(args.try_value("b"), Ok(-0.5));
+        assert_eq!(args.try_value("-n"), Ok(-2));
+        assert!(args.flag("-v"));
+    }
+
+    #[test]
+    fn flags_are_not_values() {
+        assert_eq!(
+            spec()
+                .try_parse_from(["in", "-n", "--verbose"])
+                .unwrap_err(),
+            ArgError::MissingValue {
+                option: "-n".into()
+            }
+        );
+        assert_eq!(
+            spec().try_parse_from(["-n", "--", "in"]).unwrap_err(),
+            ArgError::MissingValue {
+                option: "-n".into()
+            }
+        );
+    }
+
+    #[test]
+    fn help() {
+        let (spec, help) = spec().with_help_flag();
+        assert!(spec.parse_tokens(["--help"], Some(help)).unwrap().is_none());
+        assert!(spec.parse_tokens(["-vh"], Some(help)).unwrap().is_none());
+        assert!(spec
+            .parse_tokens(["--", "--help"], Some(help))
+            .unwrap()
+            .is_some());
+        assert!(spec.parse_tokens(["-n", "--help"], Some(help)).is_err());
+
+        let (spec, help) = ArgSpec::new().flag("-h|--human").with_help_flag();
+        let args = spec.parse_tokens(["-h"], Some(help)).unwrap().unwrap();
+        assert!(args.flag("--human"));
+    }
+}
+
+#[cfg(test)]
+mod help {
+    use super::{wrap, ArgSpec};
+    use crate::set_args;
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(wrap("a bb  ccc\ndddd", 6), ["a bb", "ccc", "dddd"]);
+        assert_eq!(wrap("abcdefgh ij", 4), ["abcdefgh", "ij"]);
+        assert!(wrap("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn sections() {
+        let _guard = set_args(&["./target/debug/head"]);
+        let spec = ArgSpec::new()
+            .about("Prints the first lines of a file.")
+            .positional("file")
+            .describe("The file to read.")
+            .flag("-v|--verbose")
+            .describe("Prints the name of the file before its content.")
+            .option("-n|--lines", 10);
+
+        assert_eq!(
+            spec.help_with_width(60),
+            "\
+Prints the first lines of a file.
+
+usage: head [options] <file>
+
+arguments:
+  file  The file to read.
+
+options:
+  -v, --verbose        Prints the name of the file before
+                       its content.
+  -n, --lines <value>  (default: 10)
+  -h, --help           Prints this help message.
+"
+        );
+    }
+
+    #[test]
+    fn long_names() {
+        let _guard = set_args(&["prog"]);
+        let spec = ArgSpec::new()
+            .option("--a-very-long-option-name", "x")
+            .describe("Does things.");
+
+        assert!(spec.help_with_width(80).contains(
+            "  --a-very-long-option-name <value>\n              Does things. (default: x)\n"
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt::Write;
+
+use super::ArgSpec;
+use crate::ARGS;
+
+/// The shells for which [`ArgSpec::completion_script`] can generate completion scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    /// The Bourne Again SHell.
+    Bash,
+    /// The Z shell.
+    Zsh,
+}
+
+/// Turns `name` into a valid shell function name.
+fn function_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("_{name}")
+}
+
+/// Wraps `s` in single quotes, so that the shell does not interpret it.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Escapes the characters that have a special meaning in the specs of the `_arguments` function
+/// of zsh.
+fn escape_zsh(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        if matches!(c, '[' | ']' | ':' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl ArgSpec {
+    /// Generates a script providing tab completion of the arguments described by this
+    /// [`ArgSpec`] in `shell`.
+    ///
+    /// Flags and options are completed when the current word starts with `-`, and file names are
+    /// completed otherwise. The script is written for the program named like the application
+    /// (see [`Args::program_name`](crate::Args::program_name)).
+    ///
+    /// With bash, the script must be sourced, for example from `~/.bashrc`. With zsh, it must
+    /// be saved as `_<program>` in one of the directories listed in `$fpath`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::{ArgSpec, Shell};
+    ///
+    /// // ./todo --completions >> ~/.bashrc
+    /// let spec = ArgSpec::new()
+    ///     .optional("file")
+    ///     .flag("-a|--all")
+    ///     .flag("--completions");
+    ///
+    /// let args = spec.parse();
+    /// if args.flag("--completions") {
+    ///     print!("{}", spec.completion_script(Shell::Bash));
+    ///     return;
+    /// }
+    /// ```
+    pub fn completion_script(&self, shell: Shell) -> String {
+        match shell {
+            Shell::Bash => self.bash_completion(ARGS.program_name()),
+            Shell::Zsh => self.zsh_completion(ARGS.program_name()),
+        }
+    }
+
+    /// Generates a bash completion script for the program `program`.
+    fn bash_completion(&self, program: &str) -> String {
+        let mut words: Vec<&str> = Vec::new();
+        for name in self.flags.iter().map(|f| &f.name) {
+            words.extend(name.split('|'));
+        }
+        for name in self.options.iter().map(|o| &o.name) {
+            words.extend(name.split('|'));
+        }
+        words.extend(["-h", "--help"]);
+
+        let function = function_name(program);
+        let mut script = String::new();
+        let _ = writeln!(script, "{function}() {{");
+        script.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+        if !self.options.is_empty() {
+            let options: Vec<&str> = self
+                .options
+                .iter()
+                .flat_map(|o| o.name.split('|'))
+                .collect();
+            script.push_str("    case \"${COMP_WORDS[COMP_CWORD-1]}\" in\n");
+            let _ = writeln!(script, "        {})", options.join("|"));
+            script.push_str("            COMPREPLY=()\n");
+            script.push_str("            return 0\n");
+            script.push_str("            ;;\n");
+            script.push_str("    esac\n");
+        }
+        script.push_str("    if [[ \"$cur\" == -* ]]; then\n");
+        let _ = writeln!(
+            script,
+            "        COMPREPLY=($(compgen -W {} -- \"$cur\"))",
+            quote(&words.join(" "))
+        );
+        script.push_str("    else\n");
+        script.push_str("        COMPREPLY=($(compgen -f -- \"$cur\"))\n");
+        script.push_str("    fi\n");
+        script.push_str("}\n");
+        let _ = writeln!(
+            script,
+            "complete -o filenames -F {function} {}",
+            quote(program)
+        );
+        script
+    }
+
+    /// Generates a zsh completion script for the program `program`.
+    fn zsh_completion(&self, program: &str) -> String {
+        let mut specs = Vec::new();
+        let mut flag_spec = |name: &str, description: &str, suffix: &str| {
+            let spellings: Vec<&str> = name.split('|').collect();
+            let description = if description.is_empty() {
+                suffix.to_owned()
+            } else {
+                format!("[{}]{suffix}", escape_zsh(description))
+            };
+            specs.push(match spellings[..] {
+                [spelling] => quote(&format!("{spelling}{description}")),
+                _ if description.is_empty() => format!(
+                    "{}{{{}}}",
+                    quote(&format!("({})", spellings.join(" "))),
+                    spellings.join(",")
+                ),
+                _ => format!(
+                    "{}{{{}}}{}",
+                    quote(&format!("({})", spellings.join(" "))),
+                    spellings.join(","),
+                    quote(&description)
+                ),
+            });
+        };
+
+        for f in &self.flags {
+            flag_spec(&f.name, &f.description, "");
+        }
+        for o in &self.options {
+            flag_spec(&o.name, &o.description, ":value:");
+        }
+        flag_spec("-h|--help", "Prints this help message.", "");
+        for p in &self.positionals {
+            let colons = if p.required { ":" } else { "::" };
+            specs.push(quote(&format!("{colons}{}:_files", escape_zsh(&p.name))));
+        }
+
+        let mut script = format!("#compdef {program}\n\n_arguments");
+        for spec in specs {
+            let _ = write!(script, " \\\n    {spec}");
+        }
+        script.push('\n');
+        script
+    }
+}
+
+#[cfg(test)]
+mod scripts {
+    use crate::ArgSpec;
+
+    fn spec() -> ArgSpec {
+        ArgSpec::new()
+            .positional("input")
+            .optional("output")
+            .flag("-v|--verbose")
+            .describe("Prints [more] details.")
+            .option("-n", 1)
+    }
+
+    #[test]
+    fn bash() {
+        let script = spec().bash_completion("my-prog");
+        assert!(script.starts_with("_my_prog() {\n"));
+        assert!(script.contains("        -n)\n"));
+        assert!(script.contains("compgen -W '-v --verbose -n -h --help' -- \"$cur\""));
+        assert!(script.ends_with("complete -o filenames -F _my_prog 'my-prog'\n"));
+    }
+
+    #[test]
+    fn zsh() {
+        assert_eq!(
+            spec().zsh_completion("prog"),
+            "\
+#compdef prog
+
+_arguments \\
+    '(-v --verbose)'{-v,--verbose}'[Prints \\[more\\] details.]' \\
+    '-n:value:' \\
+    '(-h --help)'{-h,--help}'[Prints this help message.]' \\
+    ':input:_files' \\
+    '::output:_files'
+"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::once_cell::OnceCell;
+
+/// Converts an [`OsString`] into a [`Box<str>`], replacing invalid UTF-8 sequences.
+fn lossy(s: OsString) -> Box<str> {
+    s.into_string()
+        .unwrap_or_else(|s| s.to_string_lossy().into_owned())
+        .into_boxed_str()
+}
+
+/// Returns whether `a` and `b` are the same environment variable name.
+///
+/// Names are case-insensitive on Windows.
+fn same_key(a: &str, b: &str) -> bool {
+    if cfg!(windows) {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+/// An environment variable, as a `(key, value)` pair.
+type Var = (Box<str>, Box<str>);
+
+/// Represents the environment variables of the application.
+///
+/// See [`ENV`] for more detailed information.
+pub struct Env {
+    /// The cached variables.
+    ///
+    /// The first time those variables are accessed, this cell is initialized.
+    cache: OnceCell<Box<[Var]>>,
+}
+
+impl Env {
+    /// Creates a new [`Env`] instance.
+    const fn new() -> Self {
+        Self {
+            cache: OnceCell::new(),
+        }
+    }
+
+    /// Forces the cache of this [`Env`] instance to be populated. The content of the
+    /// now-complete cache is returned.
+    fn force(&self) -> &[Var] {
+        self.cache.get_or_init(|| {
+            std::env::vars_os()
+                .map(|(key, value)| (lossy(key), lossy(value)))
+                .collect()
+        })
+    }
+
+    /// Returns the value of the environment variable `key`, or `None` if it is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ENV;
+    ///
+    /// match ENV.get("HOME") {
+    ///     Some(home) => println!("Your home directory is {home}."),
+    ///     None => println!("You don't have a home directory."),
+    /// }
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.force()
+            .iter()
+            .find(|(k, _)| same_key(k, key))
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Returns whether the environment variable `key` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ENV;
+    ///
+    /// if ENV.contains_key("NO_COLOR") {
+    ///     println!("Colors are disabled.");
+    /// }
+    /// ```
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns the number of environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ENV;
+    ///
+    /// println!("count: {}", ENV.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.force().len()
+    }
+
+    /// Returns whether the application has no environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ENV;
+    ///
+    /// if ENV.is_empty() {
+    ///     println!("The environment is empty.");
+    /// }
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.force().is_empty()
+    }
+
+    /// Returns an iterator over the environment variables, as `(key, value)` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ftkit::ENV;
+    ///
+    /// for (key, value) in ENV.iter().filter(|(key, _)| key.starts_with("CARGO_")) {
+    ///     println!("{key}={value}");
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> EnvIter<'_> {
+        EnvIter {
+            inner: self.force().iter(),
+        }
+    }
+}
+
+impl fmt::Debug for Env {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the environment variables of the application.
+///
+/// This iterator is returned by [`Env::iter`].
+#[derive(Debug, Clone)]
+pub struct EnvIter<'a> {
+    inner: std::slice::Iter<'a, Var>,
+}
+
+impl<'a> Iterator for EnvIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for EnvIter<'a> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(k, v)| (k.as_ref(), v.as_ref()))
+    }
+}
+
+impl<'a> ExactSizeIterator for EnvIter<'a> {
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a> IntoIterator for &'a Env {
+    type Item = (&'a str, &'a str);
+    type IntoIter = EnvIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The environment variables of the application.
+///
+/// The variables are read the first time this static is accessed, and cached afterwards. This
+/// means that changes made with [`std::env::set_var`] after that point are not visible through
+/// [`ENV`]. Names and values that are not valid UTF-8 are converted lossily: invalid sequences
+/// are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+///
+/// # Examples
+///
+/// Accessing a single variable:
+///
+/// ```no_run
+/// use ftkit::ENV;
+///
+/// let user = ENV.get("USER").unwrap_or("stranger");
+/// println!("Hello, {user}!");
+/// ```
+///
+/// Using a `for`-loop:
+///
+/// ```no_run
+/// use ftkit::ENV;
+///
+/// for (key, value) in ENV {
+///     println!("{key}={value}");
+/// }
+/// ```
+///
+/// Printing every variable:
+///
+/// ```no_run
+/// use ftkit::ENV;
+///
+/// println!("{ENV:#?}");
+/// ```
+pub static ENV: &Env = {
+    // See `ARGS` for why the static is a reference.
+    static STORAGE: Env = Env::new();
+    &STORAGE
+};
+
+/// Reads the environment variable `key` and parses it into a value of type `T`, or returns
+/// `default` if the variable is not set.
+///
+/// This works with any type implementing [`FromStr`], such as numbers or `bool`. Surrounding
+/// whitespace is ignored.
+///
+/// # Panics
+///
+/// This function panics if the variable is set but can't be parsed into a `T`. Silently using
+/// the default value would hide the mistake.
+///
+/// # Examples
+///
+/// ```no_run
+/// let port: u16 = ftkit::env_or("PORT", 8080);
+/// let debug = ftkit::env_or("DEBUG", false);
+///
+/// if debug {
+///     println!("Listening on port {port}.");
+/// }
+/// ```
+pub fn env_or<T>(key: &str, default: T) -> T
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    match ENV.get(key) {
+        Some(value) => match value.trim().parse() {
+            Ok(value) => value,
+            Err(err) => panic!("invalid value `{value}` for environment variable `{key}`: {err}"),
+        },
+        None => default,
+    }
+}
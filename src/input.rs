@@ -1,3 +1,7 @@
+use std::io::{self, BufRead};
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering::*;
+
 /// Reads a single line from the standard input.
 ///
 /// The terminating `\n` character is preserved, but will be absent on end of input.
@@ -28,12 +32,180 @@
 /// ```
 pub fn read_line() -> String {
     let mut result = String::new();
-    std::io::stdin()
-        .read_line(&mut result)
-        .expect("failed to read from stdin");
+    read_line_into(&mut result);
     result
 }
 
+/// Indicates that the encoding of the standard input has not been detected yet.
+const UNKNOWN: u8 = 0;
+/// Indicates that the standard input is encoded in UTF-8.
+const UTF8: u8 = 1;
+/// Indicates that the standard input is encoded in little-endian UTF-16.
+const UTF16_LE: u8 = 2;
+/// Indicates that the standard input is encoded in big-endian UTF-16.
+const UTF16_BE: u8 = 3;
+
+/// The encoding of the standard input.
+///
+/// This is detected the first time a line is read. Windows tools (PowerShell, most notably) tend
+/// to produce UTF-16 or BOM-prefixed text when their output is piped into another program.
+static ENCODING: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Appends a single line read from the standard input to `buf`, returning the number of bytes
+/// that were read. A return value of `0` indicates that the End-Of-File has been reached.
+///
+/// UTF-16 input is transparently converted to UTF-8, and byte order marks are skipped.
+///
+/// # Panics
+///
+/// This function panics if an error occurs whilst reading the standard input of the program.
+fn read_line_into(buf: &mut String) -> usize {
+    let mut stdin = std::io::stdin().lock();
+
+    // The lock is held, meaning that no other thread can detect the encoding at the same time.
+    let mut encoding = ENCODING.load(Relaxed);
+    if encoding == UNKNOWN {
+        encoding = detect_encoding(&mut stdin).expect("failed to read from stdin");
+        ENCODING.store(encoding, Relaxed);
+    }
+
+    read_encoded_line(&mut stdin, encoding, buf).expect("failed to read from stdin")
+}
+
+/// The number of code units that must look like UTF-16 before UTF-16 without a byte order mark
+/// is assumed.
+const MIN_UTF16_UNITS: usize = 4;
+
+/// The maximum number of code units inspected when looking for UTF-16 without a byte order mark.
+const MAX_UTF16_UNITS: usize = 32;
+
+/// Detects the encoding of the provided reader by looking at its first few bytes.
+///
+/// If the input starts with a byte order mark, it is consumed. Otherwise, UTF-16 is only assumed
+/// when the first code units (at least [`MIN_UTF16_UNITS`] of them) all consist of a zero byte
+/// and a non-zero byte in the same order, which is the case for texts written in a latin
+/// alphabet. A few zero bytes, as found in binary data or in the output of `find -print0`, are
+/// not enough.
+fn detect_encoding(reader: &mut impl BufRead) -> io::Result<u8> {
+    let buf = reader.fill_buf()?;
+    let (encoding, bom_len) = match buf {
+        [0xEF, 0xBB, 0xBF, ..] => (UTF8, 3),
+        [0xFF, 0xFE, ..] => (UTF16_LE, 2),
+        [0xFE, 0xFF, ..] => (UTF16_BE, 2),
+        _ if looks_like_utf16(buf, |[a, b]| a != 0 && b == 0) => (UTF16_LE, 0),
+        _ if looks_like_utf16(buf, |[a, b]| a == 0 && b != 0) => (UTF16_BE, 0),
+        _ => (UTF8, 0),
+    };
+    reader.consume(bom_len);
+    Ok(encoding)
+}
+
+/// Returns whether the first code units of `buf` all satisfy `is_ascii_unit`.
+///
+/// See [`detect_encoding`] for more information.
+fn looks_like_utf16(buf: &[u8], is_ascii_unit: impl Fn([u8; 2]) -> bool) -> bool {
+    let mut units = buf.chunks_exact(2).take(MAX_UTF16_UNITS);
+    units.len() >= MIN_UTF16_UNITS && units.all(|unit| is_ascii_unit([unit[0], unit[1]]))
+}
+
+/// Reads a single line from `reader`, decoding it according to `encoding`, and appends it to
+/// `buf`. The number of bytes read from `reader` is returned.
+///
+/// Invalid UTF-16 sequences are replaced with [`char::REPLACEMENT_CHARACTER`].
+fn read_encoded_line(
+    reader: &mut impl BufRead,
+    encoding: u8,
+    buf: &mut String,
+) -> io::Result<usize> {
+    if encoding != UTF16_LE && encoding != UTF16_BE {
+        return reader.read_line(buf);
+    }
+
+    let mut units = Vec::new();
+    let mut unit = [0u8; 2];
+    loop {
+        match reader.read_exact(&mut unit) {
+            Ok(()) => (),
+            // A trailing odd byte cannot be decoded and is simply dropped.
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(err),
+        }
+
+        let unit = match encoding {
+            UTF16_LE => u16::from_le_bytes(unit),
+            _ => u16::from_be_bytes(unit),
+        };
+        units.push(unit);
+        if unit == u16::from(b'\n') {
+            break;
+        }
+    }
+
+    buf.extend(
+        char::decode_utf16(units.iter().copied()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)),
+    );
+    Ok(units.len() * 2)
+}
+
+/// Reads the standard input until the End-Of-File, calling `f` for every line.
+///
+/// The terminating `\n` (or `\r\n`) of each line is removed before it is passed to the callback.
+/// A single buffer is reused for all lines, which makes this function cheaper than calling
+/// [`read_line`] in a loop.
+///
+/// # Panics
+///
+/// This function panics if an error occurs whilst reading the standard input of the program.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut count = 0;
+/// ftkit::for_each_line(|line| {
+///     count += 1;
+///     println!("{count}: {line}");
+/// });
+/// ```
+pub fn for_each_line(mut f: impl FnMut(&str)) {
+    let mut buf = String::new();
+    loop {
+        buf.clear();
+        if read_line_into(&mut buf) == 0 {
+            break;
+        }
+        let line = buf.strip_suffix('\n').unwrap_or(&buf);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        f(line);
+    }
+}
+
+/// Reads the standard input until the End-Of-File and splits each line into columns.
+///
+/// Columns are separated by runs of whitespace, which makes this function suitable for reading
+/// the output of commands such as `ls -l` or `ps`. Every line produces exactly one row, meaning
+/// that empty lines produce empty rows.
+///
+/// # Panics
+///
+/// This function panics if an error occurs whilst reading the standard input of the program.
+///
+/// # Examples
+///
+/// ```no_run
+/// for row in ftkit::read_table() {
+///     if let Some(name) = row.last() {
+///         println!("{name}");
+///     }
+/// }
+/// ```
+pub fn read_table() -> Vec<Vec<String>> {
+    let mut table = Vec::new();
+    for_each_line(|line| {
+        table.push(line.split_whitespace().map(String::from).collect());
+    });
+    table
+}
+
 /// Reads a number from the standard input. The function loops indefinitely until a valid number is
 /// provided. If the End-Of-File is reached, the function panics.
 ///
@@ -57,3 +229,166 @@ pub fn read_number() -> i32 {
         }
     }
 }
+
+/// Reads a number from the standard input, expanding an optional unit suffix. The function loops
+/// indefinitely until a valid number is provided. If the End-Of-File is reached, the function
+/// panics.
+///
+/// Decimal suffixes (`k`, `M`, `G`, `T`) multiply the number by powers of 1000, while binary
+/// suffixes (`Ki`, `Mi`, `Gi`, `Ti`) multiply it by powers of 1024. Suffixes may optionally be
+/// followed by a `B`, meaning that `4KiB` and `4Ki` are both read as `4096`.
+///
+/// # Panics
+///
+/// This function panics if it fails to read from the standard input of the program.
+///
+/// # Examples
+///
+/// ```no_run
+/// println!("How much memory should be allocated?");
+/// let size = ftkit::read_number_with_suffix();
+/// println!("Allocating {size} bytes.");
+/// ```
+pub fn read_number_with_suffix() -> i64 {
+    loop {
+        let s = read_line();
+        assert!(!s.is_empty(), "EOF reached :(");
+        if let Some(val) = parse_number_with_suffix(&s) {
+            break val;
+        }
+    }
+}
+
+/// Parses a number followed by an optional unit suffix.
+///
+/// See [`read_number_with_suffix`] for the list of supported suffixes.
+fn parse_number_with_suffix(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '-' && c != '+')
+        .unwrap_or(s.len());
+    let (number, suffix) = s.split_at(split);
+    let suffix = suffix.trim_start();
+    let suffix = suffix.strip_suffix('B').unwrap_or(suffix);
+
+    let multiplier: i64 = match suffix {
+        "" => 1,
+        "k" | "K" => 1_000,
+        "M" => 1_000_000,
+        "G" => 1_000_000_000,
+        "T" => 1_000_000_000_000,
+        "Ki" => 1 << 10,
+        "Mi" => 1 << 20,
+        "Gi" => 1 << 30,
+        "Ti" => 1 << 40,
+        _ => return None,
+    };
+
+    number.parse::<i64>().ok()?.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod read_number_with_suffix {
+    use super::parse_number_with_suffix;
+
+    #[test]
+    fn plain() {
+        assert_eq!(parse_number_with_suffix("42\n"), Some(42));
+        assert_eq!(parse_number_with_suffix("-7"), Some(-7));
+    }
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(parse_number_with_suffix("1k"), Some(1_000));
+        assert_eq!(parse_number_with_suffix("2M"), Some(2_000_000));
+        assert_eq!(parse_number_with_suffix("3G"), Some(3_000_000_000));
+        assert_eq!(parse_number_with_suffix("4KiB"), Some(4096));
+        assert_eq!(parse_number_with_suffix("1 MiB"), Some(1 << 20));
+        assert_eq!(parse_number_with_suffix("5kB"), Some(5_000));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse_number_with_suffix(""), None);
+        assert_eq!(parse_number_with_suffix("k"), None);
+        assert_eq!(parse_number_with_suffix("12x"), None);
+        assert_eq!(parse_number_with_suffix("B"), None);
+        assert_eq!(parse_number_with_suffix("9999999999T"), None);
+    }
+}
+
+#[cfg(test)]
+mod encoding {
+    use super::{detect_encoding, read_encoded_line, UTF16_BE, UTF16_LE, UTF8};
+
+    /// Detects the encoding of `input` and reads all of its lines.
+    fn read_all(mut input: &[u8]) -> (u8, Vec<String>) {
+        let encoding = detect_encoding(&mut input).unwrap();
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            if read_encoded_line(&mut input, encoding, &mut line).unwrap() == 0 {
+                break (encoding, lines);
+            }
+            lines.push(line);
+        }
+    }
+
+    /// Encodes `s` in UTF-16.
+    fn utf16(s: &str, big_endian: bool) -> Vec<u8> {
+        s.encode_utf16()
+            .flat_map(|u| {
+                if big_endian {
+                    u.to_be_bytes()
+                } else {
+                    u.to_le_bytes()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn utf8() {
+        assert_eq!(
+            read_all(b"ab\ncd"),
+            (UTF8, vec!["ab\n".into(), "cd".into()])
+        );
+        assert_eq!(read_all(b"\xEF\xBB\xBFab\n"), (UTF8, vec!["ab\n".into()]));
+    }
+
+    #[test]
+    fn utf16_le() {
+        let mut input = vec![0xFF, 0xFE];
+        input.extend(utf16("h\u{e9}\r\n\u{10a}\n", false));
+        assert_eq!(
+            read_all(&input),
+            (UTF16_LE, vec!["h\u{e9}\r\n".into(), "\u{10a}\n".into()])
+        );
+        assert_eq!(
+            read_all(&utf16("1234\n", false)),
+            (UTF16_LE, vec!["1234\n".into()])
+        );
+    }
+
+    #[test]
+    fn utf16_be() {
+        let mut input = vec![0xFE, 0xFF];
+        input.extend(utf16("abc", true));
+        assert_eq!(read_all(&input), (UTF16_BE, vec!["abc".into()]));
+        assert_eq!(
+            read_all(&utf16("abcd", true)),
+            (UTF16_BE, vec!["abcd".into()])
+        );
+    }
+
+    #[test]
+    fn zero_bytes_in_utf8() {
+        assert_eq!(
+            read_all(b"a\0./b\0./c\0"),
+            (UTF8, vec!["a\0./b\0./c\0".into()])
+        );
+        assert_eq!(read_all(b"\0abcdef\n"), (UTF8, vec!["\0abcdef\n".into()]));
+        // Too short to tell.
+        assert_eq!(read_all(&utf16("ab", false)).0, UTF8);
+    }
+}
@@ -12,3 +12,8 @@ pub use self::rand::*;
 
 mod args;
 pub use self::args::*;
+
+mod env;
+pub use self::env::*;
+
+mod once_cell;
@@ -1,99 +1,1279 @@
 use std::cell::Cell;
-use std::ops::{Bound, RangeBounds};
+use std::ops::{RangeBounds, RangeInclusive};
+use std::sync::atomic::Ordering::*;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+mod rng;
+pub use self::rng::*;
+
+mod dice;
+pub use self::dice::*;
+
+mod color;
+pub use self::color::*;
+
+mod text;
+pub use self::text::*;
+
+mod date;
+pub use self::date::*;
+
+mod noise;
+pub use self::noise::*;
+
+mod stats;
+pub use self::stats::*;
+
+mod deck;
+pub use self::deck::*;
+
+mod variant;
+pub use self::variant::*;
+
+pub mod distributions;
+
+pub mod fake;
+
+mod entropy;
+
+#[cfg(feature = "secure-rand")]
+mod secure;
+#[cfg(feature = "secure-rand")]
+pub use self::secure::*;
+
+/// The state of a random number generator.
+#[derive(Clone, Copy)]
+struct GeneratorState {
+    /// The seed the generator was initialized with.
+    seed: u64,
+    /// The current state of the generator.
+    state: u64,
+}
+
+impl GeneratorState {
+    /// Creates a new [`GeneratorState`] for a generator that was just seeded with `seed`.
+    fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+}
 
 thread_local! {
     /// The state of the global random number generator.
     ///
-    /// When the value equals `0`, the PRNG has not been initialized yet and its state should not
-    /// be used as a seed.
-    static RAND_STATE: Cell<u64> = Cell::new(0);
-}
-
-/// Generates a pseudo-random `u32` instance.
-///
-/// This internal function do not support bounds.
-fn next_u64() -> u64 {
-    RAND_STATE.with(|state| {
-        if state.get() == 0 {
-            let mut seed = std::time::SystemTime::UNIX_EPOCH
-                .elapsed()
-                .unwrap()
-                .as_nanos() as u64;
-
-            // Use SplitMix64 to improve the quality of the seed.
-            // Credits:
-            //   https://prng.di.unimi.it/splitmix64.c
-            seed = seed.wrapping_add(0x9e3779b97f4a7c15);
-            seed = (seed ^ seed.wrapping_shr(30)).wrapping_mul(0xbf58476d1ce4e5b9);
-            seed = (seed ^ seed.wrapping_shr(27)).wrapping_mul(0x94d049bb133111eb);
-            state.set(seed ^ seed.wrapping_shr(31));
+    /// When the value is `None`, the PRNG has not been initialized yet and must be seeded before
+    /// it can be used.
+    static RAND_STATE: Cell<Option<GeneratorState>> = const { Cell::new(None) };
+}
+
+/// Whether all threads share the same random number generator.
+///
+/// See [`set_shared_random`].
+static SHARED: AtomicBool = AtomicBool::new(false);
+
+/// The state of the random number generator shared by all threads, when [`SHARED`] is set.
+///
+/// When the value is `None`, the PRNG has not been initialized yet and must be seeded before
+/// it can be used.
+static SHARED_STATE: Mutex<Option<GeneratorState>> = Mutex::new(None);
+
+/// Scrambles the bits of `seed` using SplitMix64.
+///
+/// This is used to improve the quality of seeds that do not have much entropy.
+fn splitmix64(mut seed: u64) -> u64 {
+    // Credits:
+    //   https://prng.di.unimi.it/splitmix64.c
+    seed = seed.wrapping_add(0x9e3779b97f4a7c15);
+    seed = (seed ^ seed.wrapping_shr(30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    seed = (seed ^ seed.wrapping_shr(27)).wrapping_mul(0x94d049bb133111eb);
+    seed ^ seed.wrapping_shr(31)
+}
+
+/// Computes a seed for the random number generator from the current time.
+///
+/// The identifier of the current thread and the number of previous calls are mixed into the
+/// seed, ensuring that two calls made within the same clock tick (possibly from different
+/// threads) produce different seeds.
+fn time_seed() -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = std::time::SystemTime::UNIX_EPOCH
+        .elapsed()
+        .unwrap_or_else(|err| err.duration())
+        .as_nanos() as u64;
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    std::thread::current().id().hash(&mut hasher);
+    let discriminant = splitmix64(hasher.finish() ^ CALLS.fetch_add(1, Relaxed));
+
+    splitmix64(nanos ^ discriminant)
+}
+
+/// Computes a seed for the random number generator from the entropy provided by the operating
+/// system, falling back to the current time when it is not available.
+fn entropy_seed() -> u64 {
+    let mut seed = [0u8; 8];
+    match entropy::fill_os_random(&mut seed) {
+        Ok(()) => u64::from_ne_bytes(seed),
+        Err(_) => time_seed(),
+    }
+}
+
+/// The seed from which the generators of threads are derived.
+///
+/// When the value is `None`, no thread has initialized its generator yet and no seed was provided
+/// through [`set_random_seed`].
+static MASTER_SEED: Mutex<Option<u64>> = Mutex::new(None);
+
+/// The number of threads whose generator has been derived from the master seed.
+///
+/// This is used to give each thread its own stream of random numbers.
+static THREAD_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Reads the seed provided through the `FTKIT_SEED` environment variable, if any.
+///
+/// # Panics
+///
+/// This function panics if `FTKIT_SEED` is set but is not a valid `u64`.
+fn env_seed() -> Option<u64> {
+    match std::env::var("FTKIT_SEED") {
+        Ok(seed) => Some(
+            seed.trim()
+                .parse()
+                .expect("`FTKIT_SEED` must be a valid 64-bit unsigned integer"),
+        ),
+        Err(std::env::VarError::NotPresent) => None,
+        Err(std::env::VarError::NotUnicode(_)) => {
+            panic!("`FTKIT_SEED` must be a valid 64-bit unsigned integer")
+        }
+    }
+}
+
+/// Derives the seed of the `index`-th thread from the master seed.
+///
+/// The first thread uses the master seed directly, which means that single-threaded programs
+/// behave exactly as if [`set_random_seed`] had been called with the master seed.
+fn derive_seed(master: u64, index: u64) -> u64 {
+    match index {
+        0 => master,
+        // This is the `index`-th output of a SplitMix64 generator seeded with `master`.
+        _ => splitmix64(master.wrapping_add((index - 1).wrapping_mul(0x9e3779b97f4a7c15))),
+    }
+}
+
+/// Computes the seed used to initialize the random number generator of a thread when it is first
+/// used.
+///
+/// The seed is derived from the master seed and from the number of threads that were initialized
+/// before this one. When no master seed is known yet, it is read from the `FTKIT_SEED`
+/// environment variable or, failing that, generated from the entropy provided by the operating
+/// system. Either way, it is remembered so that the whole program can be replayed from it.
+///
+/// # Panics
+///
+/// This function panics if `FTKIT_SEED` is set but is not a valid `u64`.
+fn initial_seed() -> u64 {
+    derive_seed(master_seed(), THREAD_COUNTER.fetch_add(1, Relaxed))
+}
+
+/// Returns the master seed, choosing it if no thread has initialized its generator yet.
+///
+/// # Panics
+///
+/// This function panics if `FTKIT_SEED` is set but is not a valid `u64`.
+fn master_seed() -> u64 {
+    let mut master = MASTER_SEED.lock().unwrap_or_else(PoisonError::into_inner);
+    *master.get_or_insert_with(|| env_seed().unwrap_or_else(entropy_seed))
+}
+
+/// Creates the state of a generator that is used for the first time.
+///
+/// The generator shared by all threads uses the master seed directly, so that the whole program
+/// can be replayed from it.
+fn fresh_state() -> GeneratorState {
+    if SHARED.load(Relaxed) {
+        GeneratorState::new(master_seed())
+    } else {
+        GeneratorState::new(initial_seed())
+    }
+}
+
+/// Runs `f` with the state of the random number generator used by the current thread.
+///
+/// This is the generator of the current thread, or the one shared by all threads if
+/// [`set_shared_random`] has been called.
+fn with_state<R>(f: impl FnOnce(&mut Option<GeneratorState>) -> R) -> R {
+    if SHARED.load(Relaxed) {
+        f(&mut SHARED_STATE.lock().unwrap_or_else(PoisonError::into_inner))
+    } else {
+        RAND_STATE.with(|state| {
+            let mut current = state.get();
+            let ret = f(&mut current);
+            state.set(current);
+            ret
+        })
+    }
+}
+
+/// Runs `f` with the random number generator of the current thread.
+///
+/// `f` must not call any of the free functions of this module, as the state of the generator is
+/// only written back once `f` has returned.
+fn with_rng<R>(f: impl FnOnce(&mut Rng) -> R) -> R {
+    with_state(|state| {
+        let current = state.get_or_insert_with(fresh_state);
+        let mut rng = Rng::with_seed(current.state);
+        let ret = f(&mut rng);
+        current.state = rng.state;
+        ret
+    })
+}
+
+/// Seeds the random number generator of the current thread.
+///
+/// By default, the generator is seeded using random bytes provided by the operating system,
+/// meaning that every run of the program produces different numbers. Choosing a seed explicitly
+/// makes the generated numbers the same from one run to the next, which is useful to replay (and
+/// debug) a specific run.
+///
+/// Calling this function again resets the generator, even if it was already in use.
+///
+/// # Threads
+///
+/// Each thread has its own generator. Threads that generate their first random number after this
+/// function has been called derive their seed from `seed`, giving each of them a different but
+/// reproducible sequence. Threads are numbered in the order in which they first use their
+/// generator, so a multi-threaded program can be replayed as long as that order does not change.
+/// The same applies when the seed is provided through the `FTKIT_SEED` environment variable.
+///
+/// Threads which already used their generator are not affected, unless all threads share the
+/// same generator (see [`set_shared_random`]). In that case, the shared generator is reset for
+/// every thread. Otherwise, the shared generator starts from `seed` if it is enabled later.
+///
+/// # Seed precedence
+///
+/// The seed of the generator is chosen as follows:
+///
+/// 1. The seed passed to [`set_random_seed`], if it has been called.
+/// 2. The value of the `FTKIT_SEED` environment variable, read the first time a random number is
+///    generated. A program run as `FTKIT_SEED=42 ./my_program` always produces the same numbers,
+///    without having to modify its code.
+/// 3. Random bytes provided by the operating system, or the current time on platforms where
+///    those are not available.
+///
+/// # Examples
+///
+/// ```
+/// ftkit::set_random_seed(42);
+/// let first = ftkit::random_number(..);
+///
+/// ftkit::set_random_seed(42);
+/// assert_eq!(ftkit::random_number(..), first);
+/// ```
+pub fn set_random_seed(seed: u64) {
+    *MASTER_SEED.lock().unwrap_or_else(PoisonError::into_inner) = Some(seed);
+    // The current thread uses the first stream, so other threads start at the second one.
+    THREAD_COUNTER.store(1, Relaxed);
+    with_state(|state| *state = Some(GeneratorState::new(seed)));
+    if !SHARED.load(Relaxed) {
+        // The shared generator is not in use. It will start over from the new master seed if
+        // it is ever enabled, rather than continue a sequence derived from the previous seed.
+        *SHARED_STATE.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+/// Makes all threads share the same random number generator.
+///
+/// By default, each thread has its own generator. Even when a seed is provided, two threads
+/// produce unrelated sequences of numbers, and [`set_random_seed`] only resets the generator of
+/// the thread that calls it. After calling this function with `true`, all threads use a single
+/// generator, protected by a mutex: [`set_random_seed`] then resets it for every thread, and
+/// [`random_seed`] returns the same seed everywhere.
+///
+/// Calling this function with `false` goes back to per-thread generators, which pick up where
+/// they left off.
+///
+/// The shared generator is slower than per-thread generators, as threads must take turns to use
+/// it. Note that the numbers each thread receives still depend on the order in which threads
+/// access the generator, which changes from one run to the next.
+///
+/// # Examples
+///
+/// ```
+/// ftkit::set_shared_random(true);
+/// ftkit::set_random_seed(42);
+///
+/// let seed = std::thread::spawn(ftkit::random_seed).join().unwrap();
+/// assert_eq!(seed, 42);
+/// ```
+pub fn set_shared_random(shared: bool) {
+    SHARED.store(shared, Relaxed);
+}
+
+/// Seeds the random number generator from the `--seed` command-line argument, if present.
+///
+/// The seed can be written either as `--seed 42` or as `--seed=42`, like any option read with
+/// [`Args::option`](crate::Args::option), and is ignored after the `--` separator. When it is
+/// found, [`set_random_seed`] is called with it and the seed is returned. Otherwise, the
+/// generator is left untouched and `None` is returned.
+///
+/// Calling this function at the start of `main` makes any run of the program reproducible from
+/// the command line, for example when grading randomized exercises.
+///
+/// # Panics
+///
+/// This function panics if `--seed` has no value, or if its value is not a valid `u64`.
+///
+/// # Examples
+///
+/// ```no_run
+/// // ./my_program --seed 42
+/// ftkit::seed_from_args();
+/// println!("{}", ftkit::random_number(1..=6));
+/// ```
+pub fn seed_from_args() -> Option<u64> {
+    let seed = match crate::ARGS.option_parse("--seed") {
+        Ok(seed) => seed?,
+        Err(err) => panic!("{err}"),
+    };
+    set_random_seed(seed);
+    Some(seed)
+}
+
+/// Runs `f` with the random number generator of the current thread temporarily seeded with
+/// `seed`.
+///
+/// Once `f` returns (or panics), the generator is restored to the state it was in before this
+/// function was called. This makes it possible to test code that uses random numbers without
+/// affecting the rest of the program.
+///
+/// When all threads share the same generator (see [`set_shared_random`]), it is the shared
+/// generator that is temporarily seeded: while `f` runs, other threads draw their numbers from
+/// the sequence of `seed` too, and the numbers `f` receives depend on them.
+///
+/// # Examples
+///
+/// ```
+/// fn roll_two_dice() -> i32 {
+///     ftkit::random_number(1..=6) + ftkit::random_number(1..=6)
+/// }
+///
+/// let a = ftkit::with_seed(42, roll_two_dice);
+/// let b = ftkit::with_seed(42, roll_two_dice);
+/// assert_eq!(a, b);
+/// ```
+pub fn with_seed<R>(seed: u64, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous state of the generator, even if `f` panics.
+    struct Guard(Option<GeneratorState>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            with_state(|state| *state = self.0);
+        }
+    }
+
+    let _guard = Guard(with_state(|state| state.replace(GeneratorState::new(seed))));
+    f()
+}
+
+/// Returns the seed of the random number generator of the current thread.
+///
+/// This is the seed the generator was initialized with, whether it was chosen by the operating
+/// system, read from the `FTKIT_SEED` environment variable or passed to [`set_random_seed`]. It
+/// does not change as random numbers are generated. Printing it makes it possible to replay a
+/// run that went wrong: running the program again with `FTKIT_SEED` set to the printed value, or
+/// passing it to [`set_random_seed`], produces the exact same numbers.
+///
+/// Within [`with_seed`], the seed passed to that function is returned.
+///
+/// # Threads
+///
+/// On the first thread that generates random numbers (usually the main thread), the returned
+/// seed is the one that replays the whole program. Other threads derive their own seed from it,
+/// as explained in the documentation of [`set_random_seed`].
+///
+/// # Panics
+///
+/// This function panics if the generator was not initialized yet and `FTKIT_SEED` is set but is
+/// not a valid `u64`.
+///
+/// # Examples
+///
+/// ```no_run
+/// println!("replay with FTKIT_SEED={}", ftkit::random_seed());
+/// ```
+///
+/// ```
+/// ftkit::set_random_seed(42);
+/// ftkit::random_number(..);
+/// assert_eq!(ftkit::random_seed(), 42);
+/// ```
+pub fn random_seed() -> u64 {
+    with_state(|state| state.get_or_insert_with(fresh_state).seed)
+}
+
+/// Generates a random `u64`.
+///
+/// Every possible `u64` value is equally likely, and each bit of the result is independent from
+/// the others. This makes the returned value suitable as a source of raw random bits, for example
+/// to implement hashing schemes or custom generation algorithms.
+///
+/// # Examples
+///
+/// ```
+/// let id = ftkit::random_u64();
+/// println!("your id is {id:016x}");
+/// ```
+pub fn random_u64() -> u64 {
+    with_rng(Rng::u64)
+}
+
+/// Generates a random `usize`.
+///
+/// Every possible `usize` value is equally likely. Note that this is not suitable to generate an
+/// index within a collection; use [`random_index`] for that instead.
+///
+/// # Examples
+///
+/// ```
+/// let hash = ftkit::random_usize();
+/// println!("bucket: {}", hash % 16);
+/// ```
+pub fn random_usize() -> usize {
+    with_rng(Rng::usize)
+}
+
+/// Generates a random number within the provided bounds.
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty. For example, `12..12` is an empty range,
+/// but `12..=12` is not.
+///
+/// # Examples
+///
+/// ```
+/// # use std::ops::RangeBounds;
+/// #
+/// # macro_rules! assert_matches {
+/// #   ($e:expr, $p:pat) => {{
+/// #       match $e {
+/// #           $p => (),
+/// #           val => panic!("assert failed: {val:?} does not match {}", stringify!($p)),
+/// #       }
+/// #   }}
+/// # }
+/// assert_matches!(ftkit::random_number(..), i32::MIN..=i32::MAX);
+/// assert_matches!(ftkit::random_number(12..15), 12..=14);
+/// assert_matches!(ftkit::random_number(-15..=15), -15..=15);
+/// assert_eq!(ftkit::random_number(16..=16), 16);
+/// assert!(ftkit::random_number(0..) >= 0);
+/// ```
+pub fn random_number(range: impl RangeBounds<i32>) -> i32 {
+    with_rng(|rng| rng.number(range))
+}
+
+/// Generates a random value of any integer type within the provided bounds.
+///
+/// This is a more general version of [`random_number`], which only works with `i32`. Characters
+/// are supported as well: every valid [`char`] within the range is equally likely, and the
+/// surrogate code points, which are not valid characters, are never generated.
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty. For example, `12..12` is an empty range,
+/// but `12..=12` is not.
+///
+/// # Examples
+///
+/// ```
+/// let byte: u8 = ftkit::random(..);
+///
+/// let words = ["hello", "world"];
+/// let index = ftkit::random(0..words.len());
+/// assert!(index < words.len());
+///
+/// let big = ftkit::random(1_000_000_000_000u64..=2_000_000_000_000);
+/// assert!((1_000_000_000_000..=2_000_000_000_000).contains(&big));
+///
+/// assert_eq!(ftkit::random(-5i8..=-5), -5);
+///
+/// let letter = ftkit::random('a'..='z');
+/// assert!(letter.is_ascii_lowercase());
+/// ```
+pub fn random<T: RandomRange>(range: impl RangeBounds<T>) -> T {
+    with_rng(|rng| rng.random(range))
+}
+
+/// Generates a vector of `n` random numbers within the provided bounds.
+///
+/// This produces the same values as calling [`random_number`] `n` times in a row. The state of
+/// the generator is only loaded and stored once for the whole vector, which makes this function
+/// slightly faster than a loop (see `benches/random_vec.rs`).
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty and `n` is not zero.
+///
+/// # Examples
+///
+/// ```
+/// let grades = ftkit::random_vec(0..=20, 30);
+///
+/// assert_eq!(grades.len(), 30);
+/// assert!(grades.iter().all(|g| (0..=20).contains(g)));
+/// ```
+pub fn random_vec(range: impl RangeBounds<i32>, n: usize) -> Vec<i32> {
+    with_rng(|rng| rng.vec(range, n))
+}
+
+/// Generates a vector of `n` random values of any integer type within the provided bounds.
+///
+/// This is a more general version of [`random_vec`], which only works with `i32`. See also
+/// [`random`].
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty and `n` is not zero.
+///
+/// # Examples
+///
+/// ```
+/// let bytes: Vec<u8> = ftkit::random_vec_of(.., 1024);
+/// assert_eq!(bytes.len(), 1024);
+///
+/// let indices = ftkit::random_vec_of(0..10usize, 5);
+/// assert!(indices.iter().all(|&i| i < 10));
+/// ```
+pub fn random_vec_of<T: RandomRange>(range: impl RangeBounds<T>, n: usize) -> Vec<T> {
+    with_rng(|rng| rng.vec_of(range, n))
+}
+
+/// Generates a grid of `rows` rows and `cols` columns, filled with random numbers within the
+/// provided bounds.
+///
+/// Each number is generated as if by [`random_number`]. The grid is returned as a vector of
+/// rows, meaning that `grid[y][x]` is the number at row `y` and column `x`.
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty and the grid is not.
+///
+/// # Examples
+///
+/// ```
+/// // A board of 5x5 tiles, each worth between 1 and 9 points.
+/// let board = ftkit::random_grid(5, 5, 1..=9);
+/// for row in &board {
+///     let row: Vec<String> = row.iter().map(|n| n.to_string()).collect();
+///     println!("{}", row.join(" "));
+/// }
+///
+/// assert_eq!(board.len(), 5);
+/// assert!(board.iter().all(|row| row.len() == 5));
+/// ```
+pub fn random_grid(rows: usize, cols: usize, range: impl RangeBounds<i32>) -> Vec<Vec<i32>> {
+    with_rng(|rng| rng.grid(rows, cols, range))
+}
+
+/// Generates a grid of `rows` rows and `cols` columns, filled with random characters taken from
+/// `charset`.
+///
+/// Each character is picked uniformly among the characters of `charset`. A character that
+/// appears several times in `charset` is proportionally more likely to be picked, which makes it
+/// easy to control how dense a map is.
+///
+/// # Panics
+///
+/// This function panics if `charset` is empty.
+///
+/// # Examples
+///
+/// ```
+/// // A maze where a quarter of the cells are walls.
+/// let maze = ftkit::random_char_grid(10, 20, "#...");
+/// for row in &maze {
+///     println!("{}", row.iter().collect::<String>());
+/// }
+///
+/// assert!(maze.iter().flatten().all(|&c| c == '#' || c == '.'));
+/// ```
+pub fn random_char_grid(rows: usize, cols: usize, charset: &str) -> Vec<Vec<char>> {
+    with_rng(|rng| rng.char_grid(rows, cols, charset))
+}
+
+/// Returns an infinite iterator over random numbers within the provided bounds.
+///
+/// Each item is generated as if by [`random_number`]. This makes it possible to use the usual
+/// iterator methods, such as [`take`](Iterator::take), [`filter`](Iterator::filter) or
+/// [`collect`](Iterator::collect), to build random values.
+///
+/// # Panics
+///
+/// The returned iterator panics when advanced if the provided range is empty.
+///
+/// # Examples
+///
+/// ```
+/// let rolls: Vec<i32> = ftkit::random_numbers(1..=6).take(10).collect();
+/// assert_eq!(rolls.len(), 10);
+/// assert!(rolls.iter().all(|r| (1..=6).contains(r)));
+///
+/// // The first multiple of 7.
+/// let n = ftkit::random_numbers(..).find(|n| n % 7 == 0).unwrap();
+/// assert_eq!(n % 7, 0);
+/// ```
+pub fn random_numbers(range: impl RangeBounds<i32>) -> impl Iterator<Item = i32> {
+    let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+    std::iter::repeat_with(move || random_number(bounds))
+}
+
+/// Returns an infinite iterator over the positions of a random walk.
+///
+/// The first position is `start`. Each following position is obtained by adding a random step,
+/// generated within `step_range`, to the previous one. Positions saturate at the bounds of
+/// `i32` instead of overflowing.
+///
+/// # Panics
+///
+/// The returned iterator panics when advanced if `step_range` is empty.
+///
+/// # Examples
+///
+/// ```
+/// // A drunkard taking 10 steps, either forward or backward.
+/// for position in ftkit::random_walk(0, -1..=1).take(10) {
+///     println!("{}*", " ".repeat((position + 10) as usize));
+/// }
+///
+/// let prices: Vec<i32> = ftkit::random_walk(100, -5..=5).take(30).collect();
+/// assert_eq!(prices[0], 100);
+/// assert!(prices.windows(2).all(|w| (w[1] - w[0]).abs() <= 5));
+/// ```
+pub fn random_walk(start: i32, step_range: impl RangeBounds<i32>) -> impl Iterator<Item = i32> {
+    let bounds = (
+        step_range.start_bound().cloned(),
+        step_range.end_bound().cloned(),
+    );
+    std::iter::successors(Some(start), move |&pos| {
+        Some(pos.saturating_add(random_number(bounds)))
+    })
+}
+
+/// Generates a random `f64` uniformly distributed in `[0, 1)`.
+///
+/// The result is a multiple of `2^-53` (the precision of an `f64` around `1.0`), and every such
+/// multiple is equally likely. `0.0` can be generated, but `1.0` can't. This is the building
+/// block of most algorithms working with probabilities: for example, `next_float() < 0.25` is
+/// `true` a quarter of the time.
+///
+/// # Examples
+///
+/// ```
+/// let x = ftkit::next_float();
+/// assert!((0.0..1.0).contains(&x));
+///
+/// // Estimating pi by throwing darts at a square.
+/// let hits = (0..10_000)
+///     .filter(|_| {
+///         let (x, y) = (ftkit::next_float(), ftkit::next_float());
+///         x * x + y * y < 1.0
+///     })
+///     .count();
+/// println!("pi is about {}", 4.0 * hits as f64 / 10_000.0);
+/// ```
+pub fn next_float() -> f64 {
+    with_rng(Rng::next_float)
+}
+
+/// Generates a random floating-point number within the provided bounds.
+///
+/// An unbounded start or end is replaced with [`f64::MIN`] or [`f64::MAX`] respectively.
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty (for example, `1.0..1.0`), or if one of
+/// its bounds is not a finite number.
+///
+/// # Examples
+///
+/// ```
+/// let x = ftkit::random_float(0.0..1.0);
+/// assert!((0.0..1.0).contains(&x));
+///
+/// let y = ftkit::random_float(-2.5..=2.5);
+/// assert!((-2.5..=2.5).contains(&y));
+///
+/// assert_eq!(ftkit::random_float(4.0..=4.0), 4.0);
+/// ```
+pub fn random_float(range: impl RangeBounds<f64>) -> f64 {
+    with_rng(|rng| rng.float(range))
+}
+
+/// Generates a random point within a rectangle.
+///
+/// The `x` coordinate is generated within `x_range` and the `y` coordinate within `y_range`, as
+/// with [`random_number`].
+///
+/// # Panics
+///
+/// This function panics if one of the ranges is empty.
+///
+/// # Examples
+///
+/// ```
+/// const WIDTH: i32 = 80;
+/// const HEIGHT: i32 = 24;
+///
+/// let (x, y) = ftkit::random_point(0..WIDTH, 0..HEIGHT);
+/// assert!((0..WIDTH).contains(&x) && (0..HEIGHT).contains(&y));
+/// ```
+pub fn random_point(x_range: impl RangeBounds<i32>, y_range: impl RangeBounds<i32>) -> (i32, i32) {
+    with_rng(|rng| rng.point(x_range, y_range))
+}
+
+/// Generates a random point with floating-point coordinates within a rectangle.
+///
+/// The `x` coordinate is generated within `x_range` and the `y` coordinate within `y_range`, as
+/// with [`random_float`].
+///
+/// # Panics
+///
+/// This function panics if one of the ranges is empty or has non-finite bounds.
+///
+/// # Examples
+///
+/// ```
+/// let (x, y) = ftkit::random_point_float(-1.0..1.0, -1.0..1.0);
+/// assert!((-1.0..1.0).contains(&x) && (-1.0..1.0).contains(&y));
+/// ```
+pub fn random_point_float(
+    x_range: impl RangeBounds<f64>,
+    y_range: impl RangeBounds<f64>,
+) -> (f64, f64) {
+    with_rng(|rng| rng.point_float(x_range, y_range))
+}
+
+/// Generates a random duration within `spread` of `base`.
+///
+/// The returned duration is uniformly distributed between `base - spread` and `base + spread`,
+/// with nanosecond precision. Durations can't be negative, so the lower bound is clamped to zero
+/// when `spread` is larger than `base`.
+///
+/// Randomized delays are useful to simulate network latency, or to avoid having several clients
+/// retry an operation at the exact same time.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// // Wait about one second before retrying, give or take 200 milliseconds.
+/// let delay = ftkit::random_jitter(Duration::from_secs(1), Duration::from_millis(200));
+/// assert!(delay >= Duration::from_millis(800));
+/// assert!(delay <= Duration::from_millis(1200));
+///
+/// let delay = ftkit::random_jitter(Duration::from_millis(10), Duration::from_secs(1));
+/// assert!(delay <= Duration::from_millis(1010));
+/// ```
+pub fn random_jitter(base: Duration, spread: Duration) -> Duration {
+    with_rng(|rng| rng.jitter(base, spread))
+}
+
+/// Generates a random boolean, with equal chances of being `true` or `false`.
+///
+/// # Examples
+///
+/// ```
+/// if ftkit::random_bool() {
+///     println!("heads");
+/// } else {
+///     println!("tails");
+/// }
+/// ```
+pub fn random_bool() -> bool {
+    with_rng(Rng::bool)
+}
+
+/// Returns either `-1` or `1`, with equal chances.
+///
+/// # Examples
+///
+/// ```
+/// // A random walk along a line.
+/// let mut position = 0;
+/// for _ in 0..10 {
+///     position += ftkit::random_sign();
+/// }
+/// assert!((-10..=10).contains(&position));
+/// ```
+pub fn random_sign() -> i32 {
+    with_rng(Rng::sign)
+}
+
+/// Returns `true` with probability `p`.
+///
+/// A probability of `0.0` never returns `true`, while a probability of `1.0` always does.
+///
+/// # Panics
+///
+/// This function panics if `p` is not within `0.0..=1.0`.
+///
+/// # Examples
+///
+/// ```
+/// // The dragon drops a legendary sword 5% of the time.
+/// if ftkit::random_chance(0.05) {
+///     println!("You found a legendary sword!");
+/// }
+///
+/// assert!(ftkit::random_chance(1.0));
+/// assert!(!ftkit::random_chance(0.0));
+/// ```
+pub fn random_chance(p: f64) -> bool {
+    with_rng(|rng| rng.chance(p))
+}
+
+/// Generates a random index for a collection of length `len`.
+///
+/// The returned index is always in the range `0..len`, and every index is equally likely.
+///
+/// # Panics
+///
+/// This function panics if `len` is zero, since an empty collection has no valid index.
+///
+/// # Examples
+///
+/// ```
+/// let names = vec!["Alice", "Bob", "Charlie"];
+/// let i = ftkit::random_index(names.len());
+/// println!("{} goes first!", names[i]);
+/// ```
+pub fn random_index(len: usize) -> usize {
+    with_rng(|rng| rng.index(len))
+}
+
+/// Picks a random element of the provided slice.
+///
+/// # Panics
+///
+/// This function panics if the slice is empty. See [`try_random_choice`] for a version of this
+/// function that does not panic.
+///
+/// # Examples
+///
+/// ```
+/// let words = ["apple", "banana", "cherry"];
+/// let word = ftkit::random_choice(&words);
+/// assert!(words.contains(word));
+/// ```
+pub fn random_choice<T>(slice: &[T]) -> &T {
+    with_rng(|rng| rng.choice(slice))
+}
+
+/// Picks a random element of the provided slice, or returns `None` if the slice is empty.
+///
+/// # Examples
+///
+/// ```
+/// let enemies = ["goblin", "orc", "troll"];
+/// match ftkit::try_random_choice(&enemies) {
+///     Some(enemy) => println!("A wild {enemy} appears!"),
+///     None => println!("Nothing happens."),
+/// }
+///
+/// let empty: [i32; 0] = [];
+/// assert_eq!(ftkit::try_random_choice(&empty), None);
+/// ```
+pub fn try_random_choice<T>(slice: &[T]) -> Option<&T> {
+    with_rng(|rng| rng.try_choice(slice))
+}
+
+/// Picks a random element of the provided iterator, or returns `None` if it is empty.
+///
+/// Every element is equally likely to be picked. The iterator is consumed entirely, but its
+/// elements are never collected, meaning that this function can pick a random line of a huge
+/// file without storing the whole file in memory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::BufRead;
+///
+/// let lines = std::io::stdin().lock().lines().map(Result::unwrap);
+/// match ftkit::random_from_iter(lines) {
+///     Some(line) => println!("random line: {line}"),
+///     None => println!("the input is empty"),
+/// }
+/// ```
+///
+/// ```
+/// let even = ftkit::random_from_iter((0..100).filter(|n| n % 2 == 0)).unwrap();
+/// assert!(even % 2 == 0);
+///
+/// assert_eq!(ftkit::random_from_iter(std::iter::empty::<i32>()), None);
+/// ```
+pub fn random_from_iter<I: IntoIterator>(iter: I) -> Option<I::Item> {
+    // The iterator might itself use the generator of the current thread, so it must not be
+    // advanced from within `with_rng`.
+    let mut chosen = None;
+    for (i, item) in iter.into_iter().enumerate() {
+        if random_index(i + 1) == 0 {
+            chosen = Some(item);
         }
+    }
+    chosen
+}
 
-        // Credits:
-        //   WyRand: https://github.com/wangyi-fudan/wyhash
-        state.set(state.get().wrapping_add(0xa0761d6478bd642f));
-        let t = (state.get() as u128).wrapping_mul((state.get() ^ 0xe7037ed1a0b428db) as u128);
-        (t.wrapping_shr(64) ^ t) as u64
-    })
+/// Shuffles the elements of the provided slice in place.
+///
+/// Every possible ordering of the elements is equally likely.
+///
+/// # Examples
+///
+/// ```
+/// let mut cards = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+/// ftkit::shuffle(&mut cards);
+///
+/// cards.sort();
+/// assert_eq!(cards, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+/// ```
+pub fn shuffle<T>(slice: &mut [T]) {
+    with_rng(|rng| rng.shuffle(slice))
 }
 
-/// Generates a random number within the provided bounds.
+/// Returns a shuffled copy of the provided slice.
+///
+/// This is equivalent to cloning the slice into a [`Vec`] and calling [`shuffle`] on it, which
+/// makes it possible to shuffle data that can't be modified. Every possible ordering of the
+/// elements is equally likely.
+///
+/// Iterators can be shuffled with [`ShuffleIterator::shuffled`].
+///
+/// # Examples
+///
+/// ```
+/// const PLAYERS: [&str; 4] = ["Alice", "Bob", "Charlie", "Dave"];
+///
+/// let mut order = ftkit::shuffled(&PLAYERS);
+/// println!("{} plays first.", order[0]);
+///
+/// order.sort();
+/// assert_eq!(order, ["Alice", "Bob", "Charlie", "Dave"]);
+/// ```
+pub fn shuffled<T: Clone>(slice: &[T]) -> Vec<T> {
+    with_rng(|rng| rng.shuffled(slice))
+}
+
+/// Adds the [`shuffled`](ShuffleIterator::shuffled) method to iterators.
+///
+/// This trait is implemented for every iterator, and must be imported to be used.
+pub trait ShuffleIterator: Iterator + Sized {
+    /// Collects the items of the iterator and returns them in random order.
+    ///
+    /// Every possible ordering of the items is equally likely. The iterator must be finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::ShuffleIterator;
+    ///
+    /// for n in (1..=10).shuffled() {
+    ///     println!("{n}");
+    /// }
+    ///
+    /// let mut words: Vec<&str> = "the quick brown fox".split(' ').shuffled().collect();
+    /// words.sort();
+    /// assert_eq!(words, ["brown", "fox", "quick", "the"]);
+    /// ```
+    fn shuffled(self) -> std::vec::IntoIter<Self::Item> {
+        // The iterator might itself use the generator of the current thread, so it must not be
+        // advanced from within `with_rng`.
+        let mut items: Vec<Self::Item> = self.collect();
+        shuffle(&mut items);
+        items.into_iter()
+    }
+}
+
+impl<I: Iterator> ShuffleIterator for I {}
+
+/// Generates a random permutation of the numbers `0..n`.
+///
+/// Every number from `0` to `n - 1` appears exactly once in the returned vector, and every
+/// possible order is equally likely.
+///
+/// # Examples
+///
+/// ```
+/// let questions = ["2 + 2?", "Capital of France?", "Largest planet?"];
+///
+/// for i in ftkit::random_permutation(questions.len()) {
+///     println!("{}", questions[i]);
+/// }
+///
+/// let mut p = ftkit::random_permutation(5);
+/// p.sort();
+/// assert_eq!(p, [0, 1, 2, 3, 4]);
+/// ```
+pub fn random_permutation(n: usize) -> Vec<usize> {
+    with_rng(|rng| rng.permutation(n))
+}
+
+/// Picks `k` distinct elements of the provided slice.
+///
+/// Elements are distinct in the sense that the same position of the slice is never picked twice.
+/// Every subset of `k` elements is equally likely, and the returned elements are in random order.
 ///
 /// # Panics
 ///
-/// This function panics if the provided range is empty. For example, `12..12` is an empty range,
-/// but `12..=12` is not.
+/// This function panics if `k` is larger than the length of the slice.
 ///
 /// # Examples
 ///
 /// ```
-/// # use std::ops::RangeBounds;
-/// #
-/// # macro_rules! assert_matches {
-/// #   ($e:expr, $p:pat) => {{
-/// #       match $e {
-/// #           $p => (),
-/// #           val => panic!("assert failed: {val:?} does not match {}", stringify!($p)),
-/// #       }
-/// #   }}
-/// # }
-/// assert_matches!(ftkit::random_number(..), i32::MIN..=i32::MAX);
-/// assert_matches!(ftkit::random_number(12..15), 12..=14);
-/// assert_matches!(ftkit::random_number(-15..=15), -15..=15);
-/// assert_eq!(ftkit::random_number(16..=16), 16);
-/// assert!(ftkit::random_number(0..) >= 0);
+/// let students = ["Alice", "Bob", "Charlie", "Dave", "Eve"];
+/// let team = ftkit::random_sample(&students, 3);
+///
+/// assert_eq!(team.len(), 3);
+/// assert!(team.iter().all(|s| students.contains(s)));
 /// ```
-pub fn random_number(range: impl RangeBounds<i32>) -> i32 {
-    let min = match range.start_bound() {
-        Bound::Excluded(&n) => n
-            .checked_add(1)
-            .expect("can't generate a random number larger than i32::MAX"),
-        Bound::Included(&n) => n,
-        Bound::Unbounded => i32::MIN,
-    };
+pub fn random_sample<T: Clone>(slice: &[T], k: usize) -> Vec<T> {
+    with_rng(|rng| rng.sample(slice, k))
+}
 
-    let max = match range.end_bound() {
-        Bound::Excluded(&n) => n
-            .checked_sub(1)
-            .expect("can't generate a random number smaller than i32::MIN"),
-        Bound::Included(&n) => n,
-        Bound::Unbounded => i32::MAX,
-    };
+/// Moves `k` random elements of the provided slice to its beginning, in random order.
+///
+/// After this function returns, `slice[..k]` contains a uniformly random selection of `k`
+/// elements of the slice, in random order. The remaining elements are left in an unspecified
+/// order. Only `k` steps of a shuffle are performed, which is much faster than a full
+/// [`shuffle`] when `k` is small compared to the length of the slice.
+///
+/// # Panics
+///
+/// This function panics if `k` is larger than the length of the slice.
+///
+/// # Examples
+///
+/// ```
+/// let mut deck: Vec<u32> = (1..=10_000).collect();
+///
+/// ftkit::partial_shuffle(&mut deck, 5);
+/// let hand = &deck[..5];
+/// println!("your hand: {hand:?}");
+/// ```
+pub fn partial_shuffle<T>(slice: &mut [T], k: usize) {
+    with_rng(|rng| rng.partial_shuffle(slice, k))
+}
 
-    assert!(
-        min <= max,
-        "can't generate a random number within an empty range"
-    );
+/// Keeps each element of the provided slice independently with probability `p`.
+///
+/// The returned elements are in the same order as in the original slice. On average, the result
+/// contains `p * slice.len()` elements.
+///
+/// # Panics
+///
+/// This function panics if `p` is not within `0.0..=1.0`.
+///
+/// # Examples
+///
+/// ```
+/// let measurements: Vec<i32> = (0..1000).collect();
+///
+/// // Keep roughly 10% of the measurements.
+/// let thinned = ftkit::random_subset(&measurements, 0.1);
+/// assert!(thinned.windows(2).all(|w| w[0] < w[1]));
+///
+/// assert_eq!(ftkit::random_subset(&measurements, 1.0), measurements);
+/// assert!(ftkit::random_subset(&measurements, 0.0).is_empty());
+/// ```
+pub fn random_subset<T: Clone>(slice: &[T], p: f64) -> Vec<T> {
+    with_rng(|rng| rng.subset(slice, p))
+}
 
-    let raw = next_u64() as i32;
-    if min == i32::MIN && max == i32::MAX {
-        raw
-    } else {
-        let range_size = (max as u32).wrapping_sub(min as u32).wrapping_add(1);
-        (raw as u32)
-            .wrapping_rem(range_size)
-            .wrapping_add(min as u32) as i32
-    }
+/// Splits the provided slice into two random groups.
+///
+/// The first group contains `left_size` elements, and the second one contains the remaining
+/// elements. Every element of the slice ends up in exactly one of the groups, every way of
+/// splitting the slice is equally likely, and the elements of each group are in random order.
+///
+/// # Panics
+///
+/// This function panics if `left_size` is larger than the length of the slice.
+///
+/// # Examples
+///
+/// ```
+/// let players = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Frank"];
+/// let (red, blue) = ftkit::random_partition(&players, 3);
+/// println!("red team: {red:?}");
+/// println!("blue team: {blue:?}");
+///
+/// assert_eq!(red.len(), 3);
+/// assert_eq!(blue.len(), 3);
+/// assert!(red.iter().all(|p| !blue.contains(p)));
+/// ```
+pub fn random_partition<T: Clone>(slice: &[T], left_size: usize) -> (Vec<T>, Vec<T>) {
+    with_rng(|rng| rng.partition(slice, left_size))
+}
+
+/// Generates a random string of `len` ASCII alphanumeric characters.
+///
+/// Each character is picked uniformly among uppercase letters, lowercase letters and digits.
+///
+/// # Examples
+///
+/// ```
+/// let token = ftkit::random_string(16);
+///
+/// assert_eq!(token.len(), 16);
+/// assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+/// ```
+pub fn random_string(len: usize) -> String {
+    with_rng(|rng| rng.string(len))
+}
+
+/// Generates a random string of `len` characters taken from `charset`.
+///
+/// Each character of the string is picked uniformly among the characters of `charset`. A
+/// character that appears several times in `charset` is proportionally more likely to be picked.
+///
+/// # Panics
+///
+/// This function panics if `charset` is empty.
+///
+/// # Examples
+///
+/// ```
+/// let dna = ftkit::random_string_from("ACGT", 12);
+/// assert_eq!(dna.len(), 12);
+///
+/// let hex = ftkit::random_string_from("0123456789abcdef", 8);
+/// assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
+///
+/// let dice = ftkit::random_string_from("⚀⚁⚂⚃⚄⚅", 3);
+/// assert_eq!(dice.chars().count(), 3);
+/// ```
+pub fn random_string_from(charset: &str, len: usize) -> String {
+    with_rng(|rng| rng.string_from(charset, len))
+}
+
+/// Fills the provided buffer with random bytes.
+///
+/// The bytes are not suitable for secrets such as cryptographic keys. See [`Rng`] for more
+/// information.
+///
+/// # Examples
+///
+/// ```
+/// let mut noise = [0u8; 32];
+/// ftkit::fill_random(&mut noise);
+/// println!("noise: {noise:02x?}");
+/// ```
+pub fn fill_random(buf: &mut [u8]) {
+    with_rng(|rng| rng.fill(buf))
+}
+
+/// Generates a random version 4 UUID, as described in RFC 4122.
+///
+/// The UUID is returned in its usual textual form, such as
+/// `"f47ac10b-58cc-4372-a567-0e02b2c3d479"`.
+///
+/// # Examples
+///
+/// ```
+/// let id = ftkit::random_uuid();
+///
+/// assert_eq!(id.len(), 36);
+/// assert_eq!(&id[14..15], "4");
+/// assert_ne!(id, ftkit::random_uuid());
+/// ```
+pub fn random_uuid() -> String {
+    with_rng(Rng::uuid)
+}
+
+/// Generates a random printable ASCII character.
+///
+/// The returned character is in the range `' '..='~'`, which includes letters, digits,
+/// punctuation and the space character.
+///
+/// # Examples
+///
+/// ```
+/// let c = ftkit::random_char();
+/// assert!(c.is_ascii() && !c.is_ascii_control());
+/// ```
+pub fn random_char() -> char {
+    with_rng(Rng::char)
+}
+
+/// Generates a random character within the provided range.
+///
+/// Every valid [`char`] within the range is equally likely. This is equivalent to calling
+/// [`random`] with the same range.
+///
+/// # Panics
+///
+/// This function panics if the range is empty.
+///
+/// # Examples
+///
+/// ```
+/// let letter = ftkit::random_char_in('a'..='z');
+/// assert!(letter.is_ascii_lowercase());
+///
+/// let emoji = ftkit::random_char_in('😀'..='😎');
+/// assert!(('😀'..='😎').contains(&emoji));
+/// ```
+pub fn random_char_in(range: RangeInclusive<char>) -> char {
+    with_rng(|rng| rng.char_in(range))
+}
+
+/// Generates a random number following a normal (Gaussian) distribution.
+///
+/// Values close to `mean` are the most likely, and about 68% of the generated values are within
+/// `std_dev` of it.
+///
+/// # Panics
+///
+/// This function panics if `std_dev` is negative or if one of the parameters is not finite.
+///
+/// # Examples
+///
+/// ```
+/// // The height of a random adult, in centimeters.
+/// let height = ftkit::random_normal(170.0, 10.0);
+/// println!("{height:.1} cm");
+///
+/// assert_eq!(ftkit::random_normal(3.0, 0.0), 3.0);
+/// ```
+pub fn random_normal(mean: f64, std_dev: f64) -> f64 {
+    with_rng(|rng| rng.normal(mean, std_dev))
+}
+
+/// Picks a random element of the provided slice, proportionally to its weight.
+///
+/// Each item is a pair of a value and its weight. An item with a weight of `2.0` is twice as
+/// likely to be picked as an item with a weight of `1.0`, and an item with a weight of `0.0` is
+/// never picked.
+///
+/// # Panics
+///
+/// This function panics if a weight is negative or not finite, or if the weights sum up to zero
+/// (which includes the case of an empty slice).
+///
+/// # Examples
+///
+/// ```
+/// let loot = [("gold", 70.0), ("potion", 25.0), ("sword", 5.0), ("nothing", 0.0)];
+///
+/// let item = ftkit::random_weighted(&loot);
+/// assert_ne!(*item, "nothing");
+/// ```
+pub fn random_weighted<T>(items: &[(T, f64)]) -> &T {
+    with_rng(|rng| rng.weighted(items))
 }
 
 #[cfg(test)]
@@ -126,3 +1306,112 @@ mod random_number {
         }
     }
 }
+
+#[cfg(test)]
+mod random {
+    use super::random;
+
+    #[test]
+    fn bounds() {
+        for _ in 0..1000 {
+            assert!((250..=255).contains(&random(250u8..)));
+            assert!((-128..-120).contains(&random(..-120i8)));
+            assert!((u128::MAX - 3..=u128::MAX).contains(&random(u128::MAX - 3..)));
+            assert!((-3..3).contains(&random(-3i128..3)));
+        }
+    }
+
+    #[test]
+    fn full_range() {
+        let mut found = [false; 256];
+
+        for _ in 0..10000 {
+            found[random::<u8>(..) as usize] = true;
+        }
+
+        for (i, f) in found.iter().enumerate() {
+            assert!(f, "{i} was never generated");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty() {
+        random(5usize..5);
+    }
+}
+
+#[cfg(test)]
+mod threads {
+    use super::{
+        derive_seed, random_u64, set_random_seed, set_shared_random, splitmix64, time_seed, Rng,
+    };
+
+    #[test]
+    fn derived_seeds_are_replayable() {
+        // The first thread replays exactly like a program seeded with the master seed.
+        assert_eq!(derive_seed(1234, 0), 1234);
+        // Other threads follow the SplitMix64 sequence of the master seed. Changing those values
+        // would break the replay of runs recorded with a previous version.
+        assert_eq!(derive_seed(1234, 1), splitmix64(1234));
+        assert_eq!(
+            derive_seed(1234, 2),
+            splitmix64(1234u64.wrapping_add(0x9e3779b97f4a7c15))
+        );
+    }
+
+    #[test]
+    fn derived_seeds_are_distinct() {
+        let seeds: Vec<u64> = (0..64).map(|index| derive_seed(1234, index)).collect();
+        for (i, a) in seeds.iter().enumerate() {
+            assert!(seeds[i + 1..].iter().all(|b| a != b), "{seeds:?}");
+        }
+        assert_ne!(derive_seed(1234, 1), derive_seed(1235, 1));
+    }
+
+    #[test]
+    fn shared_sequence() {
+        set_shared_random(true);
+        set_random_seed(1234);
+        let mut numbers = vec![random_u64()];
+        numbers.extend(
+            std::thread::spawn(|| [random_u64(), random_u64()])
+                .join()
+                .unwrap(),
+        );
+        numbers.push(random_u64());
+        set_shared_random(false);
+
+        // Tests running in parallel may draw numbers from the shared generator too, so the
+        // numbers observed here are only required to appear in order within its sequence.
+        let mut reference = Rng::with_seed(1234);
+        for n in numbers {
+            assert!(
+                (0..100_000).any(|_| reference.u64() == n),
+                "{n} is not part of the sequence"
+            );
+        }
+    }
+
+    #[test]
+    fn time_seeds_are_distinct() {
+        // Those calls are likely to happen within the same clock tick.
+        let seeds: Vec<u64> = (0..4)
+            .map(|_| std::thread::spawn(time_seed))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .chain([time_seed(), time_seed()])
+            .collect();
+
+        for (i, a) in seeds.iter().enumerate() {
+            assert!(seeds[i + 1..].iter().all(|b| a != b), "{seeds:?}");
+        }
+    }
+
+    #[test]
+    fn seed_after_separator() {
+        let _guard = crate::set_args(&["prog", "--", "--seed", "5"]);
+        assert_eq!(super::seed_from_args(), None);
+    }
+}
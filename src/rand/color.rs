@@ -0,0 +1,110 @@
+use super::{with_rng, Rng};
+
+/// Converts a color from the HSL color space to RGB.
+///
+/// `hue` is in degrees (`0.0..360.0`), while `saturation` and `lightness` are in `0.0..=1.0`.
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    // Credits:
+    //   https://en.wikipedia.org/wiki/HSL_and_HSV#HSL_to_RGB
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h = hue / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+impl Rng {
+    /// Generates a random RGB color. See [`random_color`] for more information.
+    pub fn color(&mut self) -> (u8, u8, u8) {
+        let [r, g, b, ..] = self.u64().to_le_bytes();
+        (r, g, b)
+    }
+
+    /// Generates a random pastel RGB color. See [`random_pastel_color`] for more information.
+    pub fn pastel_color(&mut self) -> (u8, u8, u8) {
+        let hue = self.float(0.0..360.0);
+        let saturation = self.float(0.4..=0.7);
+        let lightness = self.float(0.75..=0.9);
+        hsl_to_rgb(hue, saturation, lightness)
+    }
+
+    /// Generates a random bright RGB color. See [`random_bright_color`] for more information.
+    pub fn bright_color(&mut self) -> (u8, u8, u8) {
+        let hue = self.float(0.0..360.0);
+        let saturation = self.float(0.8..=1.0);
+        let lightness = self.float(0.45..=0.55);
+        hsl_to_rgb(hue, saturation, lightness)
+    }
+}
+
+/// Generates a random color, as a `(red, green, blue)` triple.
+///
+/// Every possible color is equally likely.
+///
+/// # Examples
+///
+/// ```
+/// let (r, g, b) = ftkit::random_color();
+///
+/// // Print a block of that color in a terminal that supports true colors.
+/// println!("\x1b[48;2;{r};{g};{b}m    \x1b[0m");
+/// ```
+pub fn random_color() -> (u8, u8, u8) {
+    with_rng(Rng::color)
+}
+
+/// Generates a random pastel color, as a `(red, green, blue)` triple.
+///
+/// Pastel colors are light and softly saturated, which makes them pleasant as backgrounds.
+///
+/// # Examples
+///
+/// ```
+/// let (r, g, b) = ftkit::random_pastel_color();
+/// assert!(r.max(g).max(b) > 150);
+/// ```
+pub fn random_pastel_color() -> (u8, u8, u8) {
+    with_rng(Rng::pastel_color)
+}
+
+/// Generates a random bright color, as a `(red, green, blue)` triple.
+///
+/// Bright colors are strongly saturated, which makes them stand out against both dark and light
+/// backgrounds.
+///
+/// # Examples
+///
+/// ```
+/// let (r, g, b) = ftkit::random_bright_color();
+/// assert!(r.max(g).max(b) > 200);
+/// ```
+pub fn random_bright_color() -> (u8, u8, u8) {
+    with_rng(Rng::bright_color)
+}
+
+#[cfg(test)]
+mod hsl_to_rgb {
+    use super::hsl_to_rgb;
+
+    #[test]
+    fn primary_colors() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), (0, 0, 255));
+    }
+
+    #[test]
+    fn grays() {
+        assert_eq!(hsl_to_rgb(42.0, 0.0, 0.0), (0, 0, 0));
+        assert_eq!(hsl_to_rgb(42.0, 0.0, 1.0), (255, 255, 255));
+    }
+}
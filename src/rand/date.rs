@@ -0,0 +1,248 @@
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::time::{Duration, SystemTime};
+
+use super::{with_rng, Rng};
+
+/// A calendar date, such as 2024-02-29.
+///
+/// Dates can be compared with each other, the earliest date being the smallest one. A range of
+/// dates can be passed to [`random_date`] to pick a day within it.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::Date;
+///
+/// let date = Date::new(2024, 2, 29);
+/// assert_eq!(date.to_string(), "2024-02-29");
+/// assert!(date < Date::new(2024, 3, 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, such as `2024`.
+    pub year: i32,
+    /// The month, from `1` (January) to `12` (December).
+    pub month: u8,
+    /// The day of the month, starting at `1`.
+    pub day: u8,
+}
+
+/// Returns whether `year` is a leap year.
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in the provided month.
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Creates a new [`Date`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the date does not exist, such as 2023-02-29.
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        assert!(
+            (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month),
+            "invalid date: {year:04}-{month:02}-{day:02}"
+        );
+        Self { year, month, day }
+    }
+
+    /// Returns the number of days between 1970-01-01 and this date.
+    fn days_since_epoch(self) -> i64 {
+        // Credits:
+        //   Howard Hinnant, chrono-Compatible Low-Level Date Algorithms
+        //   https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    /// The inverse of [`Date::days_since_epoch`].
+    fn from_days_since_epoch(days: i64) -> Self {
+        // Credits:
+        //   https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Converts a [`SystemTime`] into a number of nanoseconds relative to the Unix epoch.
+fn to_nanos(time: SystemTime) -> i128 {
+    match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(after) => after.as_nanos() as i128,
+        Err(err) => -(err.duration().as_nanos() as i128),
+    }
+}
+
+/// The inverse of [`to_nanos`].
+fn from_nanos(nanos: i128) -> SystemTime {
+    let duration = |nanos: u128| {
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    };
+
+    if nanos >= 0 {
+        SystemTime::UNIX_EPOCH + duration(nanos as u128)
+    } else {
+        SystemTime::UNIX_EPOCH - duration(nanos.unsigned_abs())
+    }
+}
+
+/// Maps the bounds of a range of `T` to bounds of a range of `U`.
+fn map_bounds<T, U>(range: &impl RangeBounds<T>, f: impl Fn(&T) -> U) -> (Bound<U>, Bound<U>) {
+    let map = |bound: Bound<&T>| match bound {
+        Bound::Included(x) => Bound::Included(f(x)),
+        Bound::Excluded(x) => Bound::Excluded(f(x)),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+    (map(range.start_bound()), map(range.end_bound()))
+}
+
+impl Rng {
+    /// Generates a random date within the provided range. See [`random_date`] for more
+    /// information.
+    pub fn date(&mut self, range: impl RangeBounds<Date>) -> Date {
+        let (start, end) = map_bounds(&range, |date| date.days_since_epoch());
+        assert!(
+            !matches!(start, Bound::Unbounded) && !matches!(end, Bound::Unbounded),
+            "can't generate a random date within an unbounded range"
+        );
+        Date::from_days_since_epoch(self.random((start, end)))
+    }
+
+    /// Generates a random point in time within the provided range. See [`random_datetime`] for
+    /// more information.
+    pub fn datetime(&mut self, range: impl RangeBounds<SystemTime>) -> SystemTime {
+        let (start, end) = map_bounds(&range, |&time| to_nanos(time));
+        assert!(
+            !matches!(start, Bound::Unbounded) && !matches!(end, Bound::Unbounded),
+            "can't generate a random time within an unbounded range"
+        );
+        from_nanos(self.random((start, end)))
+    }
+}
+
+/// Generates a random date within the provided range.
+///
+/// Every day of the range is equally likely.
+///
+/// # Panics
+///
+/// This function panics if the range is empty or unbounded.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::Date;
+///
+/// let start = Date::new(2000, 1, 1);
+/// let end = Date::new(2024, 12, 31);
+///
+/// let birthday = ftkit::random_date(start..=end);
+/// println!("born on {birthday}");
+/// assert!((start..=end).contains(&birthday));
+/// ```
+pub fn random_date(range: impl RangeBounds<Date>) -> Date {
+    with_rng(|rng| rng.date(range))
+}
+
+/// Generates a random point in time within the provided range.
+///
+/// Every nanosecond of the range is equally likely.
+///
+/// # Panics
+///
+/// This function panics if the range is empty or unbounded.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, SystemTime};
+///
+/// // A random moment within the last 24 hours.
+/// let now = SystemTime::now();
+/// let yesterday = now - Duration::from_secs(24 * 60 * 60);
+///
+/// let time = ftkit::random_datetime(yesterday..now);
+/// assert!(yesterday <= time && time < now);
+/// ```
+pub fn random_datetime(range: impl RangeBounds<SystemTime>) -> SystemTime {
+    with_rng(|rng| rng.datetime(range))
+}
+
+#[cfg(test)]
+mod conversions {
+    use super::{from_nanos, to_nanos, Date};
+
+    #[test]
+    fn days_since_epoch() {
+        assert_eq!(Date::new(1970, 1, 1).days_since_epoch(), 0);
+        assert_eq!(Date::new(2000, 3, 1).days_since_epoch(), 11017);
+        assert_eq!(Date::new(1969, 12, 31).days_since_epoch(), -1);
+    }
+
+    #[test]
+    fn round_trip() {
+        for days in -1_000_000..1_000_000 {
+            let date = Date::from_days_since_epoch(days);
+            assert_eq!(Date::new(date.year, date.month, date.day), date);
+            assert_eq!(date.days_since_epoch(), days);
+        }
+    }
+
+    #[test]
+    fn nanos_round_trip() {
+        for nanos in [
+            0,
+            1,
+            -1,
+            1_500_000_000,
+            -1_500_000_000,
+            i64::MAX as i128 * 4,
+        ] {
+            assert_eq!(to_nanos(from_nanos(nanos)), nanos);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid() {
+        Date::new(2023, 2, 29);
+    }
+}
@@ -0,0 +1,148 @@
+use super::shuffle;
+
+/// A deck of cards, dealt in random order.
+///
+/// A [`Deck`] holds a collection of items and hands them out one at a time, in random order.
+/// Unlike picking items with [`random_choice`](crate::random_choice), the same item is never
+/// drawn twice until the deck has been [reset](Deck::reset). This is what card games and
+/// flashcard programs usually need.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::Deck;
+///
+/// let mut deck = Deck::new(["Ace", "King", "Queen", "Jack"]);
+///
+/// let first = deck.draw().unwrap();
+/// let second = deck.draw().unwrap();
+/// assert_ne!(first, second);
+///
+/// assert_eq!(deck.len(), 2);
+/// deck.reset();
+/// assert_eq!(deck.len(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Deck<T> {
+    /// The items of the deck.
+    ///
+    /// The first `drawn` items have already been drawn. The remaining ones are still in the
+    /// deck, with its top at index `drawn`.
+    cards: Vec<T>,
+    /// The number of items that have been drawn.
+    drawn: usize,
+}
+
+impl<T: Clone> Deck<T> {
+    /// Creates a new shuffled [`Deck`] containing the provided items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::Deck;
+    ///
+    /// let deck = Deck::new(1..=52);
+    /// assert_eq!(deck.len(), 52);
+    /// ```
+    pub fn new(items: impl IntoIterator<Item = T>) -> Self {
+        let mut deck = Self {
+            cards: items.into_iter().collect(),
+            drawn: 0,
+        };
+        deck.shuffle();
+        deck
+    }
+
+    /// Shuffles the items that have not been drawn yet.
+    ///
+    /// Items that were already drawn stay out of the deck.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::Deck;
+    ///
+    /// let mut deck = Deck::new(1..=10);
+    /// deck.draw();
+    ///
+    /// deck.shuffle();
+    /// assert_eq!(deck.len(), 9);
+    /// ```
+    pub fn shuffle(&mut self) {
+        shuffle(&mut self.cards[self.drawn..]);
+    }
+
+    /// Draws the item at the top of the deck.
+    ///
+    /// Returns `None` once every item has been drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::Deck;
+    ///
+    /// let mut deck = Deck::new(["What is 2 + 2?", "What is the capital of France?"]);
+    /// while let Some(question) = deck.draw() {
+    ///     println!("{question}");
+    /// }
+    ///
+    /// assert!(deck.is_empty());
+    /// ```
+    pub fn draw(&mut self) -> Option<T> {
+        // Drawn items are kept, so that they can be put back into the deck when it is reset.
+        let card = self.cards.get(self.drawn)?.clone();
+        self.drawn += 1;
+        Some(card)
+    }
+
+    /// Puts every drawn item back into the deck, and shuffles it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::Deck;
+    ///
+    /// let mut deck = Deck::new(['A', 'B', 'C']);
+    /// while deck.draw().is_some() {}
+    ///
+    /// deck.reset();
+    /// assert_eq!(deck.len(), 3);
+    /// ```
+    pub fn reset(&mut self) {
+        self.drawn = 0;
+        self.shuffle();
+    }
+}
+
+impl<T> Deck<T> {
+    /// Returns the number of items that have not been drawn yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::Deck;
+    ///
+    /// let mut deck = Deck::new(1..=3);
+    /// deck.draw();
+    /// assert_eq!(deck.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.cards.len() - self.drawn
+    }
+
+    /// Returns whether every item of the deck has been drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ftkit::Deck;
+    ///
+    /// let mut deck = Deck::new([42]);
+    /// assert!(!deck.is_empty());
+    /// deck.draw();
+    /// assert!(deck.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
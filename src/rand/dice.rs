@@ -0,0 +1,168 @@
+use super::{with_rng, Rng};
+
+/// The result of rolling dice with [`roll_detailed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roll {
+    /// The value of each individual die, in the order they were rolled.
+    pub dice: Vec<i32>,
+    /// The constant added to the sum of the dice.
+    pub modifier: i32,
+    /// The sum of the dice, plus the modifier.
+    pub total: i32,
+}
+
+/// A parsed dice specification, such as `2d6+1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DiceSpec {
+    /// The number of dice to roll.
+    count: u32,
+    /// The number of sides of each die.
+    sides: i32,
+    /// The constant added to the sum of the dice.
+    modifier: i32,
+}
+
+/// Parses a dice specification written in the standard `NdM+K` notation.
+///
+/// The number of dice `N` defaults to one, and the modifier `K` may be omitted or negative.
+/// Spaces are ignored.
+fn parse_dice(spec: &str) -> Option<DiceSpec> {
+    let spec: String = spec.chars().filter(|c| !c.is_whitespace()).collect();
+    let (count, rest) = spec.split_once(['d', 'D'])?;
+
+    let count = match count {
+        "" => 1,
+        _ => count.parse().ok()?,
+    };
+
+    let (sides, modifier) = match rest.find(['+', '-']) {
+        Some(i) => (&rest[..i], rest[i..].parse().ok()?),
+        None => (rest, 0),
+    };
+
+    // Prevent `2d+6` from being parsed as `2d6`.
+    if !sides.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let sides = sides.parse().ok()?;
+
+    if count == 0 || sides == 0 {
+        return None;
+    }
+
+    Some(DiceSpec {
+        count,
+        sides,
+        modifier,
+    })
+}
+
+impl Rng {
+    /// Rolls dice described using the standard dice notation. See [`roll`] for more information.
+    pub fn roll(&mut self, spec: &str) -> i32 {
+        self.roll_detailed(spec).total
+    }
+
+    /// Rolls dice described using the standard dice notation, returning the value of each die.
+    /// See [`roll_detailed`] for more information.
+    pub fn roll_detailed(&mut self, spec: &str) -> Roll {
+        let DiceSpec {
+            count,
+            sides,
+            modifier,
+        } = parse_dice(spec).unwrap_or_else(|| panic!("invalid dice notation: `{spec}`"));
+
+        let dice: Vec<i32> = (0..count).map(|_| self.number(1..=sides)).collect();
+        let total = dice
+            .iter()
+            .try_fold(modifier, |acc, &die| acc.checked_add(die))
+            .unwrap_or_else(|| panic!("the total of `{spec}` does not fit in an `i32`"));
+
+        Roll {
+            dice,
+            modifier,
+            total,
+        }
+    }
+}
+
+/// Rolls dice described using the standard dice notation and returns their total.
+///
+/// The notation `NdM+K` means "roll `N` dice with `M` sides each, and add `K` to the result". The
+/// number of dice defaults to one when omitted, and the modifier can be negative or omitted.
+///
+/// # Panics
+///
+/// This function panics if `spec` is not valid dice notation, or if the total does not fit in an
+/// `i32`.
+///
+/// # Examples
+///
+/// ```
+/// let damage = ftkit::roll("2d6+1");
+/// assert!((3..=13).contains(&damage));
+///
+/// assert!((1..=20).contains(&ftkit::roll("d20")));
+/// assert!((-1..=2).contains(&ftkit::roll("1d4 - 2")));
+/// ```
+pub fn roll(spec: &str) -> i32 {
+    with_rng(|rng| rng.roll(spec))
+}
+
+/// Rolls dice described using the standard dice notation, returning the value of each die along
+/// with the total.
+///
+/// See [`roll`] for a description of the notation.
+///
+/// # Panics
+///
+/// This function panics if `spec` is not valid dice notation, or if the total does not fit in an
+/// `i32`.
+///
+/// # Examples
+///
+/// ```
+/// let roll = ftkit::roll_detailed("3d6+2");
+///
+/// assert_eq!(roll.dice.len(), 3);
+/// assert!(roll.dice.iter().all(|die| (1..=6).contains(die)));
+/// assert_eq!(roll.modifier, 2);
+/// assert_eq!(roll.total, roll.dice.iter().sum::<i32>() + 2);
+/// ```
+pub fn roll_detailed(spec: &str) -> Roll {
+    with_rng(|rng| rng.roll_detailed(spec))
+}
+
+#[cfg(test)]
+mod parse_dice {
+    use super::{parse_dice, DiceSpec};
+
+    fn spec(count: u32, sides: i32, modifier: i32) -> Option<DiceSpec> {
+        Some(DiceSpec {
+            count,
+            sides,
+            modifier,
+        })
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(parse_dice("2d6+1"), spec(2, 6, 1));
+        assert_eq!(parse_dice("d20"), spec(1, 20, 0));
+        assert_eq!(parse_dice("4D8-3"), spec(4, 8, -3));
+        assert_eq!(parse_dice(" 1 d 4 + 2 "), spec(1, 4, 2));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse_dice(""), None);
+        assert_eq!(parse_dice("6"), None);
+        assert_eq!(parse_dice("2d"), None);
+        assert_eq!(parse_dice("2d+6"), None);
+        assert_eq!(parse_dice("0d6"), None);
+        assert_eq!(parse_dice("2d0"), None);
+        assert_eq!(parse_dice("-2d6"), None);
+        assert_eq!(parse_dice("2d6+"), None);
+        assert_eq!(parse_dice("2x6"), None);
+    }
+}
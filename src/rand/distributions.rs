@@ -0,0 +1,276 @@
+//! Sampling from common probability distributions.
+//!
+//! The functions of this module go beyond the uniform distributions provided by
+//! [`random_number`](crate::random_number) and [`random_float`](crate::random_float), which is
+//! useful in probability and statistics exercises.
+
+use super::{with_rng, Rng};
+
+/// Computes the natural logarithm of `k!`.
+fn ln_factorial(k: u64) -> f64 {
+    if k < 16 {
+        return (2..=k).map(|i| i as f64).product::<f64>().ln();
+    }
+
+    // Credits:
+    //   Stirling's series: https://en.wikipedia.org/wiki/Stirling%27s_approximation
+    let k = k as f64;
+    k * k.ln() - k + 0.5 * (std::f64::consts::TAU * k).ln() + 1.0 / (12.0 * k)
+        - 1.0 / (360.0 * k * k * k)
+}
+
+impl Rng {
+    /// Generates a random number following an exponential distribution. See
+    /// [`random_exponential`] for more information.
+    pub fn exponential(&mut self, lambda: f64) -> f64 {
+        assert!(
+            lambda.is_finite() && lambda > 0.0,
+            "the rate of an exponential distribution must be positive (got {lambda})"
+        );
+
+        // `1.0 - u` is never zero, meaning that its logarithm is always finite.
+        -(1.0 - self.next_float()).ln() / lambda
+    }
+
+    /// Generates a random number following a Poisson distribution. See [`random_poisson`] for
+    /// more information.
+    pub fn poisson(&mut self, lambda: f64) -> u64 {
+        assert!(
+            lambda.is_finite() && lambda >= 0.0,
+            "the mean of a Poisson distribution must not be negative (got {lambda})"
+        );
+
+        if lambda < 10.0 {
+            // Credits:
+            //   Donald Knuth, The Art of Computer Programming, Volume 2
+            let limit = (-lambda).exp();
+            let mut k = 0;
+            let mut p = self.next_float();
+            while p > limit {
+                k += 1;
+                p *= self.next_float();
+            }
+            return k;
+        }
+
+        // Knuth's algorithm requires about `lambda` random numbers per sample, which becomes slow
+        // for large means. The transformed rejection method only requires a few.
+        //
+        // Credits:
+        //   Wolfgang Hörmann, The transformed rejection method for generating Poisson random
+        //   variables (PTRS)
+        let slam = lambda.sqrt();
+        let loglam = lambda.ln();
+        let b = 0.931 + 2.53 * slam;
+        let a = -0.059 + 0.02483 * b;
+        let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+        let vr = 0.9277 - 3.6224 / (b - 2.0);
+
+        loop {
+            let u = self.next_float() - 0.5;
+            let v = self.next_float();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+
+            if us >= 0.07 && v <= vr {
+                return k as u64;
+            }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                continue;
+            }
+            if v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln()
+                <= -lambda + k * loglam - ln_factorial(k as u64)
+            {
+                return k as u64;
+            }
+        }
+    }
+
+    /// Generates a random number following a geometric distribution. See [`random_geometric`]
+    /// for more information.
+    pub fn geometric(&mut self, p: f64) -> u64 {
+        assert!(
+            p > 0.0 && p <= 1.0,
+            "the probability of a geometric distribution must be in `0.0 < p <= 1.0` (got {p})"
+        );
+
+        if p == 1.0 {
+            return 1;
+        }
+
+        // Inverse transform sampling. `u` is never zero, meaning that its logarithm is always
+        // finite. Values too large for an `u64` saturate.
+        let u = 1.0 - self.next_float();
+        (u.ln() / (1.0 - p).ln()).floor() as u64 + 1
+    }
+
+    /// Returns an infinite iterator over independent trials with success probability `p`. See
+    /// [`bernoulli`] for more information.
+    pub fn bernoulli(&mut self, p: f64) -> impl Iterator<Item = bool> + '_ {
+        assert_probability(p);
+        std::iter::repeat_with(move || self.chance(p))
+    }
+}
+
+/// Checks that `p` is a valid probability for [`bernoulli`].
+fn assert_probability(p: f64) {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "the probability of a Bernoulli trial must be between 0 and 1 (got {p})"
+    );
+}
+
+/// Generates a random number following an exponential distribution with rate `lambda`.
+///
+/// The exponential distribution describes the time between two events that happen
+/// independently at a constant average rate, such as the arrival of customers in a shop. The
+/// mean of the generated values is `1.0 / lambda`.
+///
+/// # Panics
+///
+/// This function panics if `lambda` is not a positive finite number.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::distributions::random_exponential;
+///
+/// // On average, a customer arrives every 5 minutes.
+/// let minutes = random_exponential(1.0 / 5.0);
+/// assert!(minutes >= 0.0);
+/// ```
+pub fn random_exponential(lambda: f64) -> f64 {
+    with_rng(|rng| rng.exponential(lambda))
+}
+
+/// Generates a random number following a Poisson distribution with mean `lambda`.
+///
+/// The Poisson distribution describes the number of events that happen within a fixed amount of
+/// time, when those events happen independently at a constant average rate. For example, the
+/// number of emails received in an hour.
+///
+/// # Panics
+///
+/// This function panics if `lambda` is negative or not finite.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::distributions::random_poisson;
+///
+/// // On average, 3 emails are received every hour.
+/// let emails = random_poisson(3.0);
+/// println!("{emails} new emails");
+///
+/// assert_eq!(random_poisson(0.0), 0);
+/// ```
+pub fn random_poisson(lambda: f64) -> u64 {
+    with_rng(|rng| rng.poisson(lambda))
+}
+
+/// Generates a random number following a geometric distribution with success probability `p`.
+///
+/// The geometric distribution describes the number of independent trials needed to get a first
+/// success. For example, the number of times a die has to be rolled before a six comes up. The
+/// generated values are always at least `1`, and their mean is `1.0 / p`.
+///
+/// # Panics
+///
+/// This function panics if `p` is not in `0.0 < p <= 1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::distributions::random_geometric;
+///
+/// let rolls = random_geometric(1.0 / 6.0);
+/// println!("It took {rolls} rolls to get a six.");
+///
+/// assert_eq!(random_geometric(1.0), 1);
+/// ```
+pub fn random_geometric(p: f64) -> u64 {
+    with_rng(|rng| rng.geometric(p))
+}
+
+/// Returns an infinite iterator over independent trials with success probability `p`.
+///
+/// Each trial is `true` with probability `p` and `false` otherwise, like a coin flip with a
+/// biased coin. The outcome of a trial does not depend on the previous ones.
+///
+/// # Panics
+///
+/// This function panics if `p` is not within `0.0..=1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::distributions::bernoulli;
+///
+/// // The longest streak of heads in 100 coin flips.
+/// let mut streak = 0;
+/// let mut longest = 0;
+/// for heads in bernoulli(0.5).take(100) {
+///     streak = if heads { streak + 1 } else { 0 };
+///     longest = longest.max(streak);
+/// }
+/// println!("The longest streak is {longest} heads long.");
+///
+/// assert!(bernoulli(1.0).take(10).all(|b| b));
+/// ```
+pub fn bernoulli(p: f64) -> impl Iterator<Item = bool> {
+    assert_probability(p);
+    std::iter::repeat_with(move || crate::random_chance(p))
+}
+
+#[cfg(test)]
+mod means {
+    use super::{ln_factorial, Rng};
+
+    /// Computes the mean of `n` samples produced by `f`.
+    fn mean(n: u32, mut f: impl FnMut() -> f64) -> f64 {
+        (0..n).map(|_| f()).sum::<f64>() / n as f64
+    }
+
+    #[test]
+    fn ln_factorial_matches_exact_values() {
+        let mut exact = 0.0;
+        for k in 1..100u64 {
+            exact += (k as f64).ln();
+            assert!((ln_factorial(k) - exact).abs() < 1e-9, "{k}");
+        }
+    }
+
+    #[test]
+    fn exponential_mean() {
+        let mut rng = Rng::with_seed(1);
+        let m = mean(100_000, || rng.exponential(0.5));
+        assert!((m - 2.0).abs() < 0.05, "{m}");
+    }
+
+    #[test]
+    fn poisson_mean() {
+        let mut rng = Rng::with_seed(2);
+        for lambda in [0.5, 4.0, 12.0, 250.0] {
+            let m = mean(100_000, || rng.poisson(lambda) as f64);
+            assert!(
+                (m - lambda).abs() < lambda.sqrt() * 0.02 + 0.01,
+                "{lambda}: {m}"
+            );
+        }
+    }
+
+    #[test]
+    fn geometric_mean() {
+        let mut rng = Rng::with_seed(3);
+        let m = mean(100_000, || rng.geometric(0.25) as f64);
+        assert!((m - 4.0).abs() < 0.05, "{m}");
+    }
+
+    #[test]
+    fn bernoulli_mean() {
+        let mut rng = Rng::with_seed(5);
+        let mut trials = rng.bernoulli(0.3);
+        let m = mean(100_000, || trials.next().unwrap() as u8 as f64);
+        assert!((m - 0.3).abs() < 0.01, "{m}");
+    }
+}
@@ -0,0 +1,57 @@
+use std::io;
+
+/// Fills `buf` with random bytes provided by the operating system.
+#[cfg(unix)]
+pub fn fill_os_random(buf: &mut [u8]) -> io::Result<()> {
+    use std::io::Read;
+
+    std::fs::File::open("/dev/urandom")?.read_exact(buf)
+}
+
+/// Fills `buf` with random bytes provided by the operating system.
+#[cfg(windows)]
+pub fn fill_os_random(buf: &mut [u8]) -> io::Result<()> {
+    use std::ffi::c_void;
+
+    #[link(name = "bcrypt")]
+    extern "system" {
+        fn BCryptGenRandom(algorithm: *mut c_void, buffer: *mut u8, size: u32, flags: u32) -> i32;
+    }
+
+    /// Uses the default random number generator of the system, meaning that no algorithm handle
+    /// has to be provided.
+    const BCRYPT_USE_SYSTEM_PREFERRED_RNG: u32 = 0x00000002;
+
+    for chunk in buf.chunks_mut(u32::MAX as usize) {
+        // SAFETY:
+        //  `chunk` is valid for writes of `chunk.len()` bytes, and the system-preferred generator
+        //  does not require an algorithm handle.
+        let status = unsafe {
+            BCryptGenRandom(
+                std::ptr::null_mut(),
+                chunk.as_mut_ptr(),
+                chunk.len() as u32,
+                BCRYPT_USE_SYSTEM_PREFERRED_RNG,
+            )
+        };
+
+        // Negative `NTSTATUS` values indicate failure.
+        if status < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("BCryptGenRandom failed (status: {status:#x})"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills `buf` with random bytes provided by the operating system.
+#[cfg(not(any(unix, windows)))]
+pub fn fill_os_random(_buf: &mut [u8]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "no source of entropy is available on this platform",
+    ))
+}
@@ -0,0 +1,135 @@
+//! Generation of plausible fake data.
+//!
+//! The functions of this module are useful to populate programs with test data, such as a list
+//! of users with names and email addresses.
+
+use std::net::Ipv4Addr;
+
+use super::{with_rng, Rng};
+
+/// The first names used by [`random_name`].
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Bob", "Charlie", "Chloe", "David", "Emma", "Ethan", "Fatima", "Gabriel", "Hana",
+    "Hugo", "Ines", "Jack", "Julia", "Kenji", "Lea", "Liam", "Lucas", "Maya", "Mohamed", "Nina",
+    "Noah", "Olivia", "Oscar", "Priya", "Quentin", "Rosa", "Sami", "Sofia", "Tom", "Yuki", "Zoe",
+];
+
+/// The last names used by [`random_name`].
+const LAST_NAMES: &[&str] = &[
+    "Anderson", "Bernard", "Chen", "Dubois", "Garcia", "Hansen", "Ibrahim", "Johnson", "Kim",
+    "Kowalski", "Lambert", "Martin", "Moreau", "Nakamura", "Nguyen", "Okafor", "Petit", "Rossi",
+    "Schmidt", "Silva", "Smith", "Tanaka", "Taylor", "Williams", "Yilmaz",
+];
+
+/// The domains used by [`random_email`].
+///
+/// Those domains are reserved for documentation purposes, meaning that the generated addresses
+/// can't belong to anyone.
+const DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+impl Rng {
+    /// Generates a random full name. See [`random_name`] for more information.
+    pub fn name(&mut self) -> String {
+        format!("{} {}", self.choice(FIRST_NAMES), self.choice(LAST_NAMES))
+    }
+
+    /// Generates a random email address. See [`random_email`] for more information.
+    pub fn email(&mut self) -> String {
+        let first = self.choice(FIRST_NAMES).to_lowercase();
+        let last = self.choice(LAST_NAMES).to_lowercase();
+        let domain = self.choice(DOMAINS);
+        if self.bool() {
+            format!("{first}.{last}@{domain}")
+        } else {
+            format!("{first}{}@{domain}", self.number(1..100))
+        }
+    }
+
+    /// Generates a random IPv4 address. See [`random_ipv4`] for more information.
+    pub fn ipv4(&mut self) -> Ipv4Addr {
+        // Skip the special `0.0.0.0/8` and `127.0.0.0/8` blocks, as well as multicast and
+        // reserved addresses.
+        let first = loop {
+            let first = self.random(1..=223u8);
+            if first != 127 {
+                break first;
+            }
+        };
+        let [b, c, d, ..] = self.u64().to_le_bytes();
+        Ipv4Addr::new(first, b, c, d)
+    }
+
+    /// Generates a random phone number. See [`random_phone`] for more information.
+    pub fn phone(&mut self) -> String {
+        format!(
+            "({}) 555-01{:02}",
+            self.number(200..=999),
+            self.number(0..=99)
+        )
+    }
+}
+
+/// Generates a random full name, such as `"Alice Martin"`.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::fake::random_name;
+///
+/// let name = random_name();
+/// assert_eq!(name.split(' ').count(), 2);
+/// ```
+pub fn random_name() -> String {
+    with_rng(Rng::name)
+}
+
+/// Generates a random email address, such as `"alice.martin@example.com"`.
+///
+/// The addresses use domains reserved for documentation, meaning that they can't belong to
+/// anyone.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::fake::random_email;
+///
+/// let email = random_email();
+/// assert!(email.contains('@'));
+/// ```
+pub fn random_email() -> String {
+    with_rng(Rng::email)
+}
+
+/// Generates a random IPv4 address.
+///
+/// The generated addresses avoid the loopback, multicast and reserved ranges.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::fake::random_ipv4;
+///
+/// let ip = random_ipv4();
+/// assert!(!ip.is_loopback() && !ip.is_multicast() && !ip.is_unspecified());
+/// println!("connection from {ip}");
+/// ```
+pub fn random_ipv4() -> Ipv4Addr {
+    with_rng(Rng::ipv4)
+}
+
+/// Generates a random North American phone number, such as `"(415) 555-0123"`.
+///
+/// Numbers from `555-0100` to `555-0199` are reserved for fictional use, meaning that the
+/// generated numbers can't belong to anyone.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::fake::random_phone;
+///
+/// let phone = random_phone();
+/// assert_eq!(phone.len(), 14);
+/// ```
+pub fn random_phone() -> String {
+    with_rng(Rng::phone)
+}
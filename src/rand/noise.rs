@@ -0,0 +1,121 @@
+use super::splitmix64;
+
+/// Computes a pseudo-random value in `-1.0..=1.0` for the lattice point `(x, y)`.
+///
+/// The same point always produces the same value for a given seed.
+fn lattice(seed: u64, x: i64, y: i64) -> f64 {
+    let hash = splitmix64(splitmix64(seed ^ x as u64) ^ y as u64);
+    (hash >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+/// Smooths the interpolation factor `t` so that the noise has no visible creases at lattice
+/// points.
+fn fade(t: f64) -> f64 {
+    // Credits:
+    //   Ken Perlin, Improving Noise: 6t^5 - 15t^4 + 10t^3
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Linearly interpolates between `a` and `b`.
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Computes one-dimensional value noise at `x`, with the provided seed.
+fn value_noise1(seed: u64, x: f64) -> f64 {
+    let x0 = x.floor();
+    let t = fade(x - x0);
+    let i = x0 as i64;
+    lerp(lattice(seed, i, 0), lattice(seed, i.wrapping_add(1), 0), t)
+}
+
+/// Computes two-dimensional value noise at `(x, y)`, with the provided seed.
+fn value_noise2(seed: u64, x: f64, y: f64) -> f64 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (fade(x - x0), fade(y - y0));
+    let (i, j) = (x0 as i64, y0 as i64);
+    let (i1, j1) = (i.wrapping_add(1), j.wrapping_add(1));
+
+    let top = lerp(lattice(seed, i, j), lattice(seed, i1, j), tx);
+    let bottom = lerp(lattice(seed, i, j1), lattice(seed, i1, j1), tx);
+    lerp(top, bottom, ty)
+}
+
+/// Computes one-dimensional smooth noise at `x`.
+///
+/// The returned value is in `-1.0..=1.0`. Unlike the other random functions of this crate, the
+/// same `x` always produces the same value: the noise is a smooth random curve, and this
+/// function returns its height at `x`. Nearby values of `x` produce nearby values, and the curve
+/// changes about once per unit, so `x` is usually scaled down to produce smoother variations.
+///
+/// The curve is derived from the seed of the random number generator of the current thread (see
+/// [`random_seed`](crate::random_seed)). A seeded program always produces the same noise.
+///
+/// # Examples
+///
+/// ```
+/// // A random mountain range.
+/// for column in 0..60 {
+///     let height = ftkit::noise1(column as f64 / 8.0);
+///     let height = ((height + 1.0) * 5.0) as usize;
+///     println!("{}", "#".repeat(height));
+/// }
+///
+/// assert_eq!(ftkit::noise1(1.5), ftkit::noise1(1.5));
+/// ```
+pub fn noise1(x: f64) -> f64 {
+    value_noise1(crate::random_seed(), x)
+}
+
+/// Computes two-dimensional smooth noise at `(x, y)`.
+///
+/// The returned value is in `-1.0..=1.0`. This is the two-dimensional version of [`noise1`]: it
+/// describes a smooth random landscape, and this function returns its height at `(x, y)`.
+///
+/// # Examples
+///
+/// ```
+/// // A random map, with water, grass and mountains.
+/// for y in 0..20 {
+///     let line: String = (0..60)
+///         .map(|x| match ftkit::noise2(x as f64 / 10.0, y as f64 / 5.0) {
+///             h if h < -0.2 => '~',
+///             h if h < 0.5 => '.',
+///             _ => '^',
+///         })
+///         .collect();
+///     println!("{line}");
+/// }
+/// ```
+pub fn noise2(x: f64, y: f64) -> f64 {
+    value_noise2(crate::random_seed(), x, y)
+}
+
+#[cfg(test)]
+mod value_noise {
+    use super::{value_noise1, value_noise2};
+
+    #[test]
+    fn range() {
+        for i in -1000..1000 {
+            let x = i as f64 * 0.37;
+            assert!((-1.0..=1.0).contains(&value_noise1(42, x)));
+            assert!((-1.0..=1.0).contains(&value_noise2(42, x, -x * 0.5)));
+        }
+    }
+
+    #[test]
+    fn continuous() {
+        for i in -1000..1000 {
+            let x = i as f64 * 0.01;
+            assert!((value_noise1(7, x) - value_noise1(7, x + 1e-6)).abs() < 1e-4);
+            assert!((value_noise2(7, x, x) - value_noise2(7, x + 1e-6, x)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(value_noise1(1, 3.25), value_noise1(1, 3.25));
+        assert_ne!(value_noise1(1, 3.25), value_noise1(2, 3.25));
+    }
+}
@@ -0,0 +1,786 @@
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+use std::time::Duration;
+
+/// A random number generator.
+///
+/// The free functions of this crate (such as [`random_number`](crate::random_number) or
+/// [`shuffle`](crate::shuffle)) all rely on a hidden generator, local to the current thread. An
+/// [`Rng`], on the other hand, is an independent generator owned by the program. Two generators
+/// created with the same seed always produce the same values, regardless of what the rest of the
+/// program does.
+///
+/// When the `rand_core` feature is enabled, [`Rng`] implements the `RngCore` and `SeedableRng`
+/// traits of the [`rand_core`](https://docs.rs/rand_core/0.6) crate. This allows it to be used
+/// with the rest of the `rand` ecosystem, such as its distributions or the `SliceRandom` trait.
+///
+/// # Security
+///
+/// The generator uses the WyRand algorithm, which is fast and produces good-quality numbers, but
+/// is not cryptographically secure: anyone who observes a few of its outputs can predict all of
+/// the following ones. This applies to the free functions of this crate as well. Never use them
+/// to generate secrets, such as passwords, session tokens or cryptographic keys. Enable the
+/// `secure-rand` feature and use `secure_random_bytes` or `secure_random_number` instead.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::Rng;
+///
+/// let mut a = Rng::with_seed(42);
+/// let mut b = Rng::with_seed(42);
+/// assert_eq!(a.number(0..100), b.number(0..100));
+///
+/// let mut cards = [1, 2, 3, 4, 5];
+/// a.shuffle(&mut cards);
+/// if a.bool() {
+///     println!("first card: {}", a.choice(&cards));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    /// The current state of the generator.
+    pub(super) state: u64,
+}
+
+impl Rng {
+    /// Creates a new [`Rng`].
+    ///
+    /// The seed of the new generator is taken from the generator of the current thread. This
+    /// means that the program remains reproducible when a seed is provided through
+    /// [`set_random_seed`](crate::set_random_seed) or the `FTKIT_SEED` environment variable.
+    pub fn new() -> Self {
+        Self::with_seed(crate::random_u64())
+    }
+
+    /// Creates a new [`Rng`] with the provided seed.
+    ///
+    /// Two generators created with the same seed produce the same sequence of values.
+    pub const fn with_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates a random `u64`. See [`random_u64`](crate::random_u64) for more information.
+    pub fn u64(&mut self) -> u64 {
+        // Credits:
+        //   WyRand: https://github.com/wangyi-fudan/wyhash
+        self.state = self.state.wrapping_add(0xa0761d6478bd642f);
+        let t = (self.state as u128).wrapping_mul((self.state ^ 0xe7037ed1a0b428db) as u128);
+        (t.wrapping_shr(64) ^ t) as u64
+    }
+
+    /// Generates a random `usize`. See [`random_usize`](crate::random_usize) for more
+    /// information.
+    pub fn usize(&mut self) -> usize {
+        self.u64() as usize
+    }
+
+    /// Generates a pseudo-random `u128` instance.
+    fn u128(&mut self) -> u128 {
+        (self.u64() as u128) << 64 | self.u64() as u128
+    }
+
+    /// Generates a pseudo-random `u64` instance in the range `0..n`.
+    ///
+    /// `n` must not be zero. Every value in the range is equally likely.
+    fn below(&mut self, n: u64) -> u64 {
+        debug_assert!(n != 0);
+
+        // Taking the remainder of a random value would favor small values whenever `n` does not
+        // divide 2^64. Instead, the random value is scaled to the range and the few values that
+        // would introduce a bias are rejected.
+        //
+        // Credits:
+        //   Daniel Lemire, Fast Random Integer Generation in an Interval
+        //   https://arxiv.org/abs/1805.10941
+        let mut m = self.u64() as u128 * n as u128;
+        if (m as u64) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (m as u64) < threshold {
+                m = self.u64() as u128 * n as u128;
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    /// Generates a pseudo-random `u128` instance in the range `0..n`.
+    ///
+    /// `n` must not be zero. Every value in the range is equally likely.
+    fn below_u128(&mut self, n: u128) -> u128 {
+        debug_assert!(n != 0);
+
+        // Values below `threshold` are rejected, leaving a number of possible values that is a
+        // multiple of `n`.
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let raw = self.u128();
+            if raw >= threshold {
+                break raw % n;
+            }
+        }
+    }
+
+    /// Generates a random `f64` in `[0, 1)`. See [`next_float`](crate::next_float) for more
+    /// information.
+    pub fn next_float(&mut self) -> f64 {
+        // Only the 53 most significant bits of a random `u64` are used, as this is the precision
+        // of the mantissa of an `f64`.
+        (self.u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Generates a random `i32` within the provided bounds. See
+    /// [`random_number`](crate::random_number) for more information.
+    pub fn number(&mut self, range: impl RangeBounds<i32>) -> i32 {
+        self.random(range)
+    }
+
+    /// Generates a random value of any integer type within the provided bounds. See
+    /// [`random`](crate::random) for more information.
+    pub fn random<T: RandomRange>(&mut self, range: impl RangeBounds<T>) -> T {
+        T::random_range(self, range.start_bound(), range.end_bound())
+    }
+
+    /// Generates `n` random `i32`s within the provided bounds. See
+    /// [`random_vec`](crate::random_vec) for more information.
+    pub fn vec(&mut self, range: impl RangeBounds<i32>, n: usize) -> Vec<i32> {
+        self.vec_of(range, n)
+    }
+
+    /// Generates `n` random values of any integer type within the provided bounds. See
+    /// [`random_vec_of`](crate::random_vec_of) for more information.
+    pub fn vec_of<T: RandomRange>(&mut self, range: impl RangeBounds<T>, n: usize) -> Vec<T> {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        (0..n).map(|_| T::random_range(self, start, end)).collect()
+    }
+
+    /// Generates a grid of random numbers within the provided bounds. See
+    /// [`random_grid`](crate::random_grid) for more information.
+    pub fn grid(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        range: impl RangeBounds<i32>,
+    ) -> Vec<Vec<i32>> {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        (0..rows).map(|_| self.vec(bounds, cols)).collect()
+    }
+
+    /// Generates a grid of random characters taken from `charset`. See
+    /// [`random_char_grid`](crate::random_char_grid) for more information.
+    pub fn char_grid(&mut self, rows: usize, cols: usize, charset: &str) -> Vec<Vec<char>> {
+        let charset: Vec<char> = charset.chars().collect();
+        assert!(
+            !charset.is_empty(),
+            "can't generate a random grid from an empty character set"
+        );
+
+        (0..rows)
+            .map(|_| (0..cols).map(|_| *self.choice(&charset)).collect())
+            .collect()
+    }
+
+    /// Returns an infinite iterator over random numbers within the provided bounds. See
+    /// [`random_numbers`](crate::random_numbers) for more information.
+    pub fn numbers(&mut self, range: impl RangeBounds<i32>) -> impl Iterator<Item = i32> + '_ {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        std::iter::repeat_with(move || self.number(bounds))
+    }
+
+    /// Returns an iterator over the positions of a random walk. See
+    /// [`random_walk`](crate::random_walk) for more information.
+    pub fn walk(
+        &mut self,
+        start: i32,
+        step_range: impl RangeBounds<i32>,
+    ) -> impl Iterator<Item = i32> + '_ {
+        let bounds = (
+            step_range.start_bound().cloned(),
+            step_range.end_bound().cloned(),
+        );
+        std::iter::successors(Some(start), move |&pos| {
+            Some(pos.saturating_add(self.number(bounds)))
+        })
+    }
+
+    /// Generates a random floating-point number within the provided bounds. See
+    /// [`random_float`](crate::random_float) for more information.
+    pub fn float(&mut self, range: impl RangeBounds<f64>) -> f64 {
+        let (min, min_included) = match range.start_bound() {
+            Bound::Excluded(&n) => (n, false),
+            Bound::Included(&n) => (n, true),
+            Bound::Unbounded => (f64::MIN, true),
+        };
+
+        let (max, max_included) = match range.end_bound() {
+            Bound::Excluded(&n) => (n, false),
+            Bound::Included(&n) => (n, true),
+            Bound::Unbounded => (f64::MAX, true),
+        };
+
+        assert!(
+            min.is_finite() && max.is_finite(),
+            "can't generate a random float within a range with non-finite bounds"
+        );
+
+        if min == max && min_included && max_included {
+            return min;
+        }
+
+        assert!(
+            min < max,
+            "can't generate a random float within an empty range"
+        );
+
+        loop {
+            // This form of interpolation can't overflow, even when `min` and `max` are very far
+            // apart. Rounding errors might still produce values outside of the range, in which
+            // case we simply try again.
+            let t = self.next_float();
+            let val = min * (1.0 - t) + max * t;
+
+            let above_min = val > min || (min_included && val == min);
+            let below_max = val < max || (max_included && val == max);
+            if above_min && below_max {
+                break val;
+            }
+        }
+    }
+
+    /// Generates a random point within a rectangle. See [`random_point`](crate::random_point)
+    /// for more information.
+    pub fn point(
+        &mut self,
+        x_range: impl RangeBounds<i32>,
+        y_range: impl RangeBounds<i32>,
+    ) -> (i32, i32) {
+        (self.number(x_range), self.number(y_range))
+    }
+
+    /// Generates a random point with floating-point coordinates within a rectangle. See
+    /// [`random_point_float`](crate::random_point_float) for more information.
+    pub fn point_float(
+        &mut self,
+        x_range: impl RangeBounds<f64>,
+        y_range: impl RangeBounds<f64>,
+    ) -> (f64, f64) {
+        (self.float(x_range), self.float(y_range))
+    }
+
+    /// Generates a random duration around `base`. See [`random_jitter`](crate::random_jitter)
+    /// for more information.
+    pub fn jitter(&mut self, base: Duration, spread: Duration) -> Duration {
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+        let min = base.saturating_sub(spread).as_nanos();
+        let max = base.saturating_add(spread).as_nanos();
+        let nanos = self.random(min..=max);
+        Duration::new(
+            (nanos / NANOS_PER_SEC) as u64,
+            (nanos % NANOS_PER_SEC) as u32,
+        )
+    }
+
+    /// Generates a random boolean. See [`random_bool`](crate::random_bool) for more
+    /// information.
+    pub fn bool(&mut self) -> bool {
+        // The high bits of the generator are of slightly better quality than the low ones.
+        self.u64() >> 63 == 1
+    }
+
+    /// Returns either `-1` or `1`. See [`random_sign`](crate::random_sign) for more information.
+    pub fn sign(&mut self) -> i32 {
+        if self.bool() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Returns `true` with probability `p`. See [`random_chance`](crate::random_chance) for more
+    /// information.
+    pub fn chance(&mut self, p: f64) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "probability must be between 0 and 1 (got {p})"
+        );
+        self.next_float() < p
+    }
+
+    /// Generates a random index for a collection of length `len`. See
+    /// [`random_index`](crate::random_index) for more information.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(
+            len != 0,
+            "can't generate a random index for an empty collection"
+        );
+        self.below(len as u64) as usize
+    }
+
+    /// Picks a random element of the provided slice. See
+    /// [`random_choice`](crate::random_choice) for more information.
+    pub fn choice<'a, T>(&mut self, slice: &'a [T]) -> &'a T {
+        self.try_choice(slice)
+            .expect("can't choose an element from an empty slice")
+    }
+
+    /// Picks a random element of the provided slice, or returns `None` if the slice is empty.
+    /// See [`try_random_choice`](crate::try_random_choice) for more information.
+    pub fn try_choice<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            None
+        } else {
+            Some(&slice[self.index(slice.len())])
+        }
+    }
+
+    /// Picks a random element of the provided iterator, or returns `None` if it is empty. See
+    /// [`random_from_iter`](crate::random_from_iter) for more information.
+    pub fn choice_from_iter<I: IntoIterator>(&mut self, iter: I) -> Option<I::Item> {
+        // Credits:
+        //   Reservoir sampling: https://en.wikipedia.org/wiki/Reservoir_sampling
+        let mut chosen = None;
+        for (i, item) in iter.into_iter().enumerate() {
+            if self.index(i + 1) == 0 {
+                chosen = Some(item);
+            }
+        }
+        chosen
+    }
+
+    /// Shuffles the elements of the provided slice in place. See [`shuffle`](crate::shuffle)
+    /// for more information.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        // Credits:
+        //   Fisher-Yates shuffle: https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle
+        for i in (1..slice.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a shuffled copy of the provided slice. See [`shuffled`](crate::shuffled) for more
+    /// information.
+    pub fn shuffled<T: Clone>(&mut self, slice: &[T]) -> Vec<T> {
+        let mut vec = slice.to_vec();
+        self.shuffle(&mut vec);
+        vec
+    }
+
+    /// Generates a random permutation of `0..n`. See
+    /// [`random_permutation`](crate::random_permutation) for more information.
+    pub fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut permutation: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut permutation);
+        permutation
+    }
+
+    /// Picks `k` distinct elements of the provided slice. See
+    /// [`random_sample`](crate::random_sample) for more information.
+    pub fn sample<T: Clone>(&mut self, slice: &[T], k: usize) -> Vec<T> {
+        assert!(
+            k <= slice.len(),
+            "can't pick {k} elements from a slice of length {}",
+            slice.len()
+        );
+
+        let mut indices: Vec<usize> = (0..slice.len()).collect();
+        self.partial_shuffle(&mut indices, k);
+        indices[..k].iter().map(|&i| slice[i].clone()).collect()
+    }
+
+    /// Moves `k` random elements of the provided slice to its beginning. See
+    /// [`partial_shuffle`](crate::partial_shuffle) for more information.
+    pub fn partial_shuffle<T>(&mut self, slice: &mut [T], k: usize) {
+        assert!(
+            k <= slice.len(),
+            "can't shuffle {k} elements of a slice of length {}",
+            slice.len()
+        );
+
+        // This is a Fisher-Yates shuffle that stops once the first `k` elements are chosen.
+        for i in 0..k {
+            let j = i + self.below((slice.len() - i) as u64) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Keeps each element of the provided slice with probability `p`. See
+    /// [`random_subset`](crate::random_subset) for more information.
+    pub fn subset<T: Clone>(&mut self, slice: &[T], p: f64) -> Vec<T> {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "probability must be between 0 and 1 (got {p})"
+        );
+
+        slice
+            .iter()
+            .filter(|_| self.next_float() < p)
+            .cloned()
+            .collect()
+    }
+
+    /// Splits the provided slice into two random groups. See
+    /// [`random_partition`](crate::random_partition) for more information.
+    pub fn partition<T: Clone>(&mut self, slice: &[T], left_size: usize) -> (Vec<T>, Vec<T>) {
+        assert!(
+            left_size <= slice.len(),
+            "can't put {left_size} elements in a group from a slice of length {}",
+            slice.len()
+        );
+
+        let mut left = self.shuffled(slice);
+        let right = left.split_off(left_size);
+        (left, right)
+    }
+
+    /// Generates a random string of `len` ASCII alphanumeric characters. See
+    /// [`random_string`](crate::random_string) for more information.
+    pub fn string(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| ALPHANUMERIC[self.index(ALPHANUMERIC.len())] as char)
+            .collect()
+    }
+
+    /// Generates a random string of `len` characters taken from `charset`. See
+    /// [`random_string_from`](crate::random_string_from) for more information.
+    pub fn string_from(&mut self, charset: &str, len: usize) -> String {
+        let charset: Vec<char> = charset.chars().collect();
+        assert!(
+            !charset.is_empty(),
+            "can't generate a random string from an empty character set"
+        );
+
+        (0..len).map(|_| *self.choice(&charset)).collect()
+    }
+
+    /// Fills the provided buffer with random bytes. See [`fill_random`](crate::fill_random) for
+    /// more information.
+    pub fn fill(&mut self, buf: &mut [u8]) {
+        // Each call to the generator produces 8 bytes at once.
+        let mut chunks = buf.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.u64().to_le_bytes());
+        }
+
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            rest.copy_from_slice(&self.u64().to_le_bytes()[..rest.len()]);
+        }
+    }
+
+    /// Generates a random version 4 UUID. See [`random_uuid`](crate::random_uuid) for more
+    /// information.
+    pub fn uuid(&mut self) -> String {
+        let mut bytes = [0u8; 16];
+        self.fill(&mut bytes);
+
+        // Credits:
+        //   RFC 4122, section 4.4: https://www.rfc-editor.org/rfc/rfc4122#section-4.4
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let mut uuid = String::with_capacity(36);
+        for (i, byte) in bytes.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                uuid.push('-');
+            }
+            uuid.push_str(&format!("{byte:02x}"));
+        }
+        uuid
+    }
+
+    /// Generates a random printable ASCII character. See [`random_char`](crate::random_char)
+    /// for more information.
+    pub fn char(&mut self) -> char {
+        self.char_in(' '..='~')
+    }
+
+    /// Generates a random character within the provided range. See
+    /// [`random_char_in`](crate::random_char_in) for more information.
+    pub fn char_in(&mut self, range: RangeInclusive<char>) -> char {
+        let (start, end) = range.into_inner();
+        char::random_range(self, Bound::Included(&start), Bound::Included(&end))
+    }
+
+    /// Generates a random number following a normal (Gaussian) distribution. See
+    /// [`random_normal`](crate::random_normal) for more information.
+    pub fn normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        assert!(
+            mean.is_finite() && std_dev.is_finite() && std_dev >= 0.0,
+            "invalid normal distribution parameters (mean: {mean}, standard deviation: {std_dev})"
+        );
+
+        // Credits:
+        //   Box-Muller transform: https://en.wikipedia.org/wiki/Box%E2%80%93Muller_transform
+        //
+        // `u1` must not be zero, as its logarithm is taken.
+        let u1 = 1.0 - self.next_float();
+        let u2 = self.next_float();
+        let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+        mean + std_dev * z
+    }
+
+    /// Picks a random element of the provided slice, proportionally to its weight. See
+    /// [`random_weighted`](crate::random_weighted) for more information.
+    pub fn weighted<'a, T>(&mut self, items: &'a [(T, f64)]) -> &'a T {
+        let mut total = 0.0;
+        for (_, weight) in items {
+            assert!(
+                weight.is_finite() && *weight >= 0.0,
+                "invalid weight: {weight}"
+            );
+            total += weight;
+        }
+        assert!(total > 0.0, "the sum of the weights must not be zero");
+
+        let target = self.next_float() * total;
+        let mut cumulative = 0.0;
+        let mut last = None;
+        for (value, weight) in items {
+            if *weight == 0.0 {
+                continue;
+            }
+            cumulative += weight;
+            if target < cumulative {
+                return value;
+            }
+            last = Some(value);
+        }
+
+        // Rounding errors might make `cumulative` slightly smaller than `total`. In that case,
+        // the last item that can be picked is the right one.
+        last.unwrap()
+    }
+}
+
+impl Default for Rng {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for Rng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.fill(dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for Rng {
+    type Seed = [u8; 8];
+
+    #[inline]
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::with_seed(u64::from_le_bytes(seed))
+    }
+
+    /// Creates a new [`Rng`] with the provided seed.
+    ///
+    /// Unlike most implementations of this method, the seed is used as-is. This makes it
+    /// equivalent to [`Rng::with_seed`].
+    #[inline]
+    fn seed_from_u64(state: u64) -> Self {
+        Self::with_seed(state)
+    }
+}
+
+/// Types that can be randomly generated within a range.
+///
+/// This trait is implemented for all primitive integer types, as well as for [`char`]. See
+/// [`random`](crate::random) for more information.
+pub trait RandomRange: Sized {
+    /// Generates a random value between the `start` and `end` bounds using `rng`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the range described by `start` and `end` is empty.
+    fn random_range(rng: &mut Rng, start: Bound<&Self>, end: Bound<&Self>) -> Self;
+}
+
+/// Implements [`RandomRange`] for integer types.
+///
+/// Each signed or unsigned type `$t` is associated with the unsigned type `$u` of the same size,
+/// with the `$next` method used to generate enough random bits for it, and with the `$below`
+/// method used to generate a bounded value of that size.
+macro_rules! impl_random_range {
+    ($($next:ident, $below:ident: $($t:ty => $u:ty),*;)*) => {
+        $($(
+            impl RandomRange for $t {
+                fn random_range(rng: &mut Rng, start: Bound<&Self>, end: Bound<&Self>) -> Self {
+                    let min = match start {
+                        Bound::Excluded(&n) => n.checked_add(1).expect(concat!(
+                            "can't generate a random number larger than ",
+                            stringify!($t),
+                            "::MAX"
+                        )),
+                        Bound::Included(&n) => n,
+                        Bound::Unbounded => <$t>::MIN,
+                    };
+
+                    let max = match end {
+                        Bound::Excluded(&n) => n.checked_sub(1).expect(concat!(
+                            "can't generate a random number smaller than ",
+                            stringify!($t),
+                            "::MIN"
+                        )),
+                        Bound::Included(&n) => n,
+                        Bound::Unbounded => <$t>::MAX,
+                    };
+
+                    assert!(
+                        min <= max,
+                        "can't generate a random number within an empty range"
+                    );
+
+                    let range_size = (max as $u).wrapping_sub(min as $u).wrapping_add(1);
+                    if range_size == 0 {
+                        // The range covers every possible value of the type.
+                        rng.$next() as $t
+                    } else {
+                        (rng.$below(range_size as _) as $u).wrapping_add(min as $u) as $t
+                    }
+                }
+            }
+        )*)*
+    };
+}
+
+impl_random_range! {
+    u64, below:
+        u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize,
+        i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize;
+    u128, below_u128:
+        u128 => u128, i128 => u128;
+}
+
+impl RandomRange for char {
+    fn random_range(rng: &mut Rng, start: Bound<&Self>, end: Bound<&Self>) -> Self {
+        // Characters are generated as indices that skip the surrogate gap, which makes every
+        // valid character within the range equally likely.
+        let min = match start {
+            Bound::Excluded(&c) => char_to_index(c)
+                .checked_add(1)
+                .filter(|&index| index <= char_to_index(char::MAX))
+                .expect("can't generate a random character larger than char::MAX"),
+            Bound::Included(&c) => char_to_index(c),
+            Bound::Unbounded => 0,
+        };
+
+        let max = match end {
+            Bound::Excluded(&c) => char_to_index(c)
+                .checked_sub(1)
+                .expect("can't generate a random character smaller than '\\0'"),
+            Bound::Included(&c) => char_to_index(c),
+            Bound::Unbounded => char_to_index(char::MAX),
+        };
+
+        assert!(
+            min <= max,
+            "can't generate a random character within an empty range"
+        );
+
+        index_to_char(min + rng.below((max - min) as u64 + 1) as u32)
+    }
+}
+
+/// The characters used by [`Rng::string`].
+const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// The first surrogate code point. Surrogates are not valid [`char`]s.
+const SURROGATE_START: u32 = 0xD800;
+/// The number of surrogate code points.
+const SURROGATE_COUNT: u32 = 0x800;
+
+/// Converts a [`char`] into an index that ignores the gap left by surrogate code points.
+fn char_to_index(c: char) -> u32 {
+    let c = c as u32;
+    if c >= SURROGATE_START {
+        c - SURROGATE_COUNT
+    } else {
+        c
+    }
+}
+
+/// The inverse of [`char_to_index`].
+fn index_to_char(index: u32) -> char {
+    let c = if index >= SURROGATE_START {
+        index + SURROGATE_COUNT
+    } else {
+        index
+    };
+    char::from_u32(c).expect("invalid character index")
+}
+
+#[cfg(test)]
+mod random_char {
+    use super::Rng;
+
+    #[test]
+    fn surrogate_gap() {
+        let mut rng = Rng::with_seed(4);
+        for _ in 0..1000 {
+            let c: char = rng.random('\u{d7ff}'..='\u{e000}');
+            assert!(c == '\u{d7ff}' || c == '\u{e000}', "{c:?}");
+        }
+
+        // Both ends of the gap are excluded.
+        let bounds = (
+            std::ops::Bound::Excluded('\u{d7fe}'),
+            std::ops::Bound::Excluded('\u{e000}'),
+        );
+        assert_eq!(rng.random(bounds), '\u{d7ff}');
+    }
+}
+
+#[cfg(test)]
+mod uniformity {
+    use super::Rng;
+    use crate::check_uniform;
+
+    #[test]
+    fn small_range() {
+        let mut rng = Rng::with_seed(1);
+        let check = check_uniform((0..100_000).map(|_| rng.random(0..10usize)), 10);
+        assert!(check.passed, "{check:?}");
+    }
+
+    #[test]
+    fn large_range() {
+        // With a modulo-based implementation, numbers in the lower half of this range are twice
+        // as likely as those in its upper half.
+        const N: u64 = u64::MAX / 3 * 2;
+
+        let mut rng = Rng::with_seed(2);
+        let samples = (0..100_000).map(|_| (rng.random(0..N) >= N / 2) as usize);
+        let check = check_uniform(samples, 2);
+        assert!(check.passed, "{check:?}");
+    }
+
+    #[test]
+    fn large_range_u128() {
+        const N: u128 = u128::MAX / 3 * 2;
+
+        let mut rng = Rng::with_seed(3);
+        let samples = (0..100_000).map(|_| (rng.random(0..N) >= N / 2) as usize);
+        let check = check_uniform(samples, 2);
+        assert!(check.passed, "{check:?}");
+    }
+}
@@ -0,0 +1,98 @@
+use std::ops::{Bound, RangeBounds};
+
+use super::entropy::fill_os_random;
+
+/// Fills `buf` with random bytes provided by the operating system.
+///
+/// # Panics
+///
+/// This function panics if the operating system fails to provide random bytes.
+fn fill_secure(buf: &mut [u8]) {
+    if let Err(err) = fill_os_random(buf) {
+        panic!("failed to generate secure random bytes: {err}");
+    }
+}
+
+/// Generates `len` cryptographically secure random bytes.
+///
+/// Unlike [`fill_random`](crate::fill_random) and the other random functions of this crate, the
+/// bytes are provided by the random number generator of the operating system, which is designed
+/// to be unpredictable. They are suitable for secrets, such as passwords, session tokens or
+/// cryptographic keys. Generating them is much slower, and they can't be replayed with
+/// [`set_random_seed`](crate::set_random_seed).
+///
+/// This function is only available when the `secure-rand` feature is enabled.
+///
+/// # Panics
+///
+/// This function panics if the operating system fails to provide random bytes.
+///
+/// # Examples
+///
+/// ```
+/// let key = ftkit::secure_random_bytes(32);
+/// assert_eq!(key.len(), 32);
+/// ```
+pub fn secure_random_bytes(len: usize) -> Vec<u8> {
+    let mut buf = vec![0u8; len];
+    fill_secure(&mut buf);
+    buf
+}
+
+/// Generates a cryptographically secure random number within the provided bounds.
+///
+/// This is the secure version of [`random_number`](crate::random_number): every number of the
+/// range is equally likely, and the result can't be predicted from previously generated numbers.
+/// See [`secure_random_bytes`] for more information.
+///
+/// This function is only available when the `secure-rand` feature is enabled.
+///
+/// # Panics
+///
+/// This function panics if the provided range is empty, or if the operating system fails to
+/// provide random bytes.
+///
+/// # Examples
+///
+/// ```
+/// // A 6-digit verification code.
+/// let code = ftkit::secure_random_number(0..1_000_000);
+/// println!("Your code is {code:06}.");
+///
+/// assert!((0..1_000_000).contains(&code));
+/// ```
+pub fn secure_random_number(range: impl RangeBounds<i32>) -> i32 {
+    let min = match range.start_bound() {
+        Bound::Excluded(&n) => n
+            .checked_add(1)
+            .expect("can't generate a random number larger than i32::MAX"),
+        Bound::Included(&n) => n,
+        Bound::Unbounded => i32::MIN,
+    };
+
+    let max = match range.end_bound() {
+        Bound::Excluded(&n) => n
+            .checked_sub(1)
+            .expect("can't generate a random number smaller than i32::MIN"),
+        Bound::Included(&n) => n,
+        Bound::Unbounded => i32::MAX,
+    };
+
+    assert!(
+        min <= max,
+        "can't generate a random number within an empty range"
+    );
+
+    // The size of the range is at most 2^32, so a `u64` holds it without overflowing. Values in
+    // the incomplete chunk at the top of the `u64` range are rejected to avoid any bias.
+    let range_size = (max as i64 - min as i64 + 1) as u64;
+    let limit = u64::MAX - u64::MAX % range_size;
+    loop {
+        let mut bytes = [0u8; 8];
+        fill_secure(&mut bytes);
+        let value = u64::from_ne_bytes(bytes);
+        if value < limit {
+            return (min as i64 + (value % range_size) as i64) as i32;
+        }
+    }
+}
@@ -0,0 +1,149 @@
+/// The critical values of the chi-square distribution at `p = 0.001`, indexed by number of
+/// degrees of freedom minus one.
+const CRITICAL_VALUES: [f64; 30] = [
+    10.828, 13.816, 16.266, 18.467, 20.515, 22.458, 24.322, 26.124, 27.877, 29.588, 31.264, 32.909,
+    34.528, 36.123, 37.697, 39.252, 40.790, 42.312, 43.820, 45.315, 46.797, 48.268, 49.728, 51.179,
+    52.620, 54.052, 55.476, 56.892, 58.301, 59.703,
+];
+
+/// Returns the critical value of the chi-square distribution with `df` degrees of freedom at
+/// `p = 0.001`.
+fn critical_value(df: usize) -> f64 {
+    if let Some(&value) = CRITICAL_VALUES.get(df.wrapping_sub(1)) {
+        return value;
+    }
+
+    // Credits:
+    //   Wilson & Hilferty (1931), The distribution of chi-square.
+    const Z: f64 = 3.090_232; // The 0.999 quantile of the standard normal distribution.
+    let k = df as f64;
+    let a = 2.0 / (9.0 * k);
+    k * (1.0 - a + Z * a.sqrt()).powi(3)
+}
+
+/// The result of [`check_uniform`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformityCheck {
+    /// The chi-square statistic of the samples.
+    ///
+    /// The further the samples are from an even spread, the larger this value.
+    pub chi_square: f64,
+    /// The number of degrees of freedom of the test, which is the number of bins minus one.
+    pub degrees_of_freedom: usize,
+    /// The value above which [`chi_square`](Self::chi_square) is considered too large.
+    pub critical_value: f64,
+    /// Whether the samples look uniformly distributed.
+    pub passed: bool,
+}
+
+/// Checks whether `samples` look uniformly distributed over `bins` bins.
+///
+/// Each sample is the index of the bin it falls in, and must be smaller than `bins`. This
+/// function performs a [chi-square test]: it counts the samples in each bin, and measures how far
+/// those counts are from what a uniform distribution would produce. The test fails when the
+/// counts are so uneven that a uniform generator would produce them less than once in a thousand
+/// tries.
+///
+/// This is useful to check that a random algorithm does not favor some values over others. Note
+/// that a correct algorithm still fails the test once in a thousand runs on average, and that the
+/// test is only meaningful when each bin is expected to receive at least a few samples.
+///
+/// [chi-square test]: https://en.wikipedia.org/wiki/Pearson%27s_chi-squared_test
+///
+/// # Panics
+///
+/// This function panics if `bins` is smaller than `2`, if `samples` is empty, or if a sample is
+/// not smaller than `bins`.
+///
+/// # Examples
+///
+/// ```
+/// let rolls = (0..60_000).map(|_| ftkit::random_number(1..=6));
+/// let check = ftkit::check_uniform(rolls.map(|roll| (roll - 1) as usize), 6);
+/// println!("chi-square: {:.2}", check.chi_square);
+///
+/// // Two dice do not produce uniform sums: 7 is much more likely than 2.
+/// let sums = (0..60_000).map(|_| ftkit::random_number(1..=6) + ftkit::random_number(1..=6));
+/// let check = ftkit::check_uniform(sums.map(|sum| (sum - 2) as usize), 11);
+/// assert!(!check.passed);
+/// ```
+pub fn check_uniform(samples: impl IntoIterator<Item = usize>, bins: usize) -> UniformityCheck {
+    assert!(bins >= 2, "at least two bins are needed (got {bins})");
+
+    let mut counts = vec![0u64; bins];
+    for sample in samples {
+        assert!(sample < bins, "sample {sample} does not fit in {bins} bins");
+        counts[sample] += 1;
+    }
+
+    let total: u64 = counts.iter().sum();
+    assert!(total != 0, "at least one sample is needed");
+
+    let expected = total as f64 / bins as f64;
+    let chi_square = counts
+        .iter()
+        .map(|&c| (c as f64 - expected).powi(2) / expected)
+        .sum();
+
+    let degrees_of_freedom = bins - 1;
+    let critical_value = critical_value(degrees_of_freedom);
+    UniformityCheck {
+        chi_square,
+        degrees_of_freedom,
+        critical_value,
+        passed: chi_square < critical_value,
+    }
+}
+
+/// Runs `f` `trials` times and returns the fraction of runs that returned `true`.
+///
+/// This is the basis of the [Monte Carlo method]: when `f` performs a random experiment and
+/// reports whether it succeeded, the returned value approaches the probability of success as the
+/// number of trials grows.
+///
+/// [Monte Carlo method]: https://en.wikipedia.org/wiki/Monte_Carlo_method
+///
+/// # Panics
+///
+/// This function panics if `trials` is zero.
+///
+/// # Examples
+///
+/// ```
+/// // The probability of a point of the unit square falling inside the unit circle is pi / 4.
+/// let ratio = ftkit::monte_carlo(100_000, || {
+///     let (x, y) = (ftkit::next_float(), ftkit::next_float());
+///     x * x + y * y < 1.0
+/// });
+/// let pi = 4.0 * ratio;
+/// assert!((pi - std::f64::consts::PI).abs() < 0.05);
+///
+/// // The probability of rolling a double with two dice is 1 / 6.
+/// let ratio = ftkit::monte_carlo(100_000, || ftkit::roll("1d6") == ftkit::roll("1d6"));
+/// assert!((ratio - 1.0 / 6.0).abs() < 0.01);
+/// ```
+pub fn monte_carlo(trials: u64, mut f: impl FnMut() -> bool) -> f64 {
+    assert!(trials != 0, "at least one trial is needed");
+
+    let mut successes = 0u64;
+    for _ in 0..trials {
+        if f() {
+            successes += 1;
+        }
+    }
+    successes as f64 / trials as f64
+}
+
+#[cfg(test)]
+mod critical_value {
+    use super::{critical_value, CRITICAL_VALUES};
+
+    #[test]
+    fn approximation_matches_table() {
+        // The approximation is used past the end of the table, so it must be close to it there.
+        let last = CRITICAL_VALUES.len();
+        let relative =
+            (critical_value(last + 1) - CRITICAL_VALUES[last - 1]) / CRITICAL_VALUES[last - 1];
+        assert!((0.0..0.05).contains(&relative), "{relative}");
+    }
+}
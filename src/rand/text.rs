@@ -0,0 +1,173 @@
+use super::{with_rng, Rng};
+
+/// The consonants used by [`random_word`].
+const CONSONANTS: &[u8] = b"bcdfghjklmnprstvz";
+/// The vowels used by [`random_word`].
+const VOWELS: &[u8] = b"aeiou";
+
+/// The words used by [`random_sentence`] and [`random_paragraph`].
+const LOREM_IPSUM: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "reprehenderit",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "fugiat",
+    "nulla",
+    "pariatur",
+    "excepteur",
+    "sint",
+    "occaecat",
+    "cupidatat",
+    "non",
+    "proident",
+    "sunt",
+    "culpa",
+    "qui",
+    "officia",
+    "deserunt",
+    "mollit",
+    "anim",
+    "id",
+    "est",
+    "laborum",
+];
+
+impl Rng {
+    /// Generates a pronounceable random word of `len` letters. See [`random_word`] for more
+    /// information.
+    pub fn word(&mut self, len: usize) -> String {
+        let mut vowel = self.bool();
+        (0..len)
+            .map(|_| {
+                let letters = if vowel { VOWELS } else { CONSONANTS };
+                vowel = !vowel;
+                *self.choice(letters) as char
+            })
+            .collect()
+    }
+
+    /// Generates a random sentence of `words` words. See [`random_sentence`] for more
+    /// information.
+    pub fn sentence(&mut self, words: usize) -> String {
+        let mut sentence = String::new();
+        for i in 0..words {
+            let word = self.choice(LOREM_IPSUM);
+            if i == 0 {
+                let mut chars = word.chars();
+                sentence.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+                sentence.push_str(chars.as_str());
+            } else {
+                sentence.push(' ');
+                sentence.push_str(word);
+            }
+        }
+        if words != 0 {
+            sentence.push('.');
+        }
+        sentence
+    }
+
+    /// Generates a random paragraph of `sentences` sentences. See [`random_paragraph`] for more
+    /// information.
+    pub fn paragraph(&mut self, sentences: usize) -> String {
+        let sentences: Vec<String> = (0..sentences)
+            .map(|_| {
+                let words = self.random(4..=12);
+                self.sentence(words)
+            })
+            .collect();
+        sentences.join(" ")
+    }
+}
+
+/// Generates a pronounceable random word of `len` lowercase letters.
+///
+/// The word alternates between consonants and vowels, which makes it easy to read out loud
+/// despite not meaning anything.
+///
+/// # Examples
+///
+/// ```
+/// let name = ftkit::random_word(6);
+/// println!("Welcome to the planet {name}!");
+///
+/// assert_eq!(name.len(), 6);
+/// assert!(name.chars().all(|c| c.is_ascii_lowercase()));
+/// ```
+pub fn random_word(len: usize) -> String {
+    with_rng(|rng| rng.word(len))
+}
+
+/// Generates a random "lorem ipsum" sentence of `words` words.
+///
+/// The sentence starts with a capital letter and ends with a period. It is made of Latin-looking
+/// words, which makes it useful as filler text. When `words` is zero, an empty string is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// let sentence = ftkit::random_sentence(8);
+/// println!("{sentence}");
+///
+/// assert_eq!(sentence.split(' ').count(), 8);
+/// assert!(sentence.ends_with('.'));
+/// ```
+pub fn random_sentence(words: usize) -> String {
+    with_rng(|rng| rng.sentence(words))
+}
+
+/// Generates a random "lorem ipsum" paragraph of `sentences` sentences.
+///
+/// Each sentence contains between 4 and 12 words. Sentences are separated by a single space.
+///
+/// # Examples
+///
+/// ```
+/// let paragraph = ftkit::random_paragraph(5);
+/// println!("{paragraph}");
+///
+/// assert_eq!(paragraph.matches('.').count(), 5);
+/// ```
+pub fn random_paragraph(sentences: usize) -> String {
+    with_rng(|rng| rng.paragraph(sentences))
+}
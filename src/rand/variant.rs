@@ -0,0 +1,119 @@
+use super::{random_index, Rng};
+
+/// Enumerations whose variants can be picked at random.
+///
+/// This trait is usually implemented with the [`random_variant!`](crate::random_variant) macro,
+/// which takes care of listing the variants of a fieldless enumeration.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::RandomVariant;
+///
+/// #[derive(Debug)]
+/// enum Coin {
+///     Heads,
+///     Tails,
+/// }
+///
+/// impl RandomVariant for Coin {
+///     const COUNT: usize = 2;
+///
+///     fn from_index(index: usize) -> Self {
+///         match index {
+///             0 => Coin::Heads,
+///             1 => Coin::Tails,
+///             _ => panic!("invalid variant index"),
+///         }
+///     }
+/// }
+///
+/// println!("{:?}", Coin::random());
+/// ```
+pub trait RandomVariant: Sized {
+    /// The number of variants of the enumeration.
+    const COUNT: usize;
+
+    /// Returns the variant at position `index`, in declaration order.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is not smaller than [`COUNT`](Self::COUNT).
+    fn from_index(index: usize) -> Self;
+
+    /// Picks a random variant.
+    ///
+    /// Every variant is equally likely.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the enumeration has no variants.
+    fn random() -> Self {
+        Self::from_index(random_index(Self::COUNT))
+    }
+
+    /// Picks a random variant using `rng`.
+    ///
+    /// See [`random`](Self::random) for more information.
+    fn random_with(rng: &mut Rng) -> Self {
+        Self::from_index(rng.index(Self::COUNT))
+    }
+}
+
+/// Defines a fieldless enumeration and implements [`RandomVariant`] for it.
+///
+/// The enumeration is written as usual within the macro, and may have attributes, a visibility
+/// and documentation. Its variants can't have fields or explicit discriminants.
+///
+/// # Examples
+///
+/// ```
+/// use ftkit::RandomVariant;
+///
+/// ftkit::random_variant! {
+///     #[derive(Debug, Clone, Copy, PartialEq)]
+///     enum Direction {
+///         North,
+///         South,
+///         East,
+///         West,
+///     }
+/// }
+///
+/// let direction = Direction::random();
+/// println!("The monster moves {direction:?}.");
+///
+/// assert_eq!(Direction::COUNT, 4);
+/// assert_eq!(Direction::from_index(2), Direction::East);
+/// ```
+#[macro_export]
+macro_rules! random_variant {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $crate::RandomVariant for $name {
+            const COUNT: usize = [$(stringify!($variant)),*].len();
+
+            fn from_index(index: usize) -> Self {
+                [$(Self::$variant),*]
+                    .into_iter()
+                    .nth(index)
+                    .expect("invalid variant index")
+            }
+        }
+    };
+}